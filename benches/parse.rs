@@ -0,0 +1,21 @@
+use std::str::FromStr;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use usci::UsciCode;
+
+fn parse_benchmark(c: &mut Criterion) {
+  let mut rng = rand::thread_rng();
+  let codes: Vec<String> = (0..1000).map(|_| UsciCode::from_random(&mut rng).to_string()).collect();
+  let mut group = c.benchmark_group("parse");
+  group.throughput(Throughput::Elements(codes.len() as u64));
+  group.bench_function("from_str", |b| {
+    b.iter(|| {
+      for code in codes.iter() {
+        black_box(UsciCode::from_str(black_box(code.as_str())).unwrap());
+      }
+    })
+  });
+  group.finish();
+}
+
+criterion_group!(benches, parse_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,140 @@
+use std::fmt::{Display, Formatter};
+use crate::division;
+use crate::usci::UsciCode;
+
+const DIVISION_SUFFIXES: [&str; 6] = ["特别行政区", "自治区", "自治州", "地区", "盟", "省"];
+const ETHNIC_SUFFIXES: [&str; 20] = [
+  "朝鲜族", "土家族", "苗族", "藏族", "羌族", "彝族", "布依族", "侗族", "哈尼族", "壮族",
+  "傣族", "白族", "景颇族", "傈僳族", "回族", "蒙古族", "蒙古", "柯尔克孜", "哈萨克", "维吾尔"
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Confidence {
+  Low,
+  Medium,
+  High
+}
+
+impl Confidence {
+
+  pub fn get_code(&self) -> &'static str {
+    match self {
+      Confidence::Low => "low",
+      Confidence::Medium => "medium",
+      Confidence::High => "high"
+    }
+  }
+
+}
+
+impl Display for Confidence {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.get_code())
+  }
+}
+
+// 福建省 -> 福建, 延边朝鲜族自治州 -> 延边, 广西壮族自治区 -> 广西. A
+// single char left over, as in 县, is too ambiguous to look for.
+pub(crate) fn short_name(name: &str) -> Option<&str> {
+  let mut short = name.strip_suffix('市').unwrap_or(name);
+  if let Some(stripped) = DIVISION_SUFFIXES.iter().find_map(|suffix| short.strip_suffix(suffix)) {
+    short = stripped;
+  }
+  while let Some(stripped) = ETHNIC_SUFFIXES.iter().find_map(|suffix| short.strip_suffix(suffix)) {
+    short = stripped;
+  }
+  if short == name || short.chars().count() < 2 {
+    return None;
+  }
+  return Some(short);
+}
+
+// Placeholder prefectures such as 市辖区 and 省直辖县级行政区划 name no place.
+pub(crate) fn is_placeholder(name: &str) -> bool {
+  return name == "市辖区" || name == "县" || name.ends_with("直辖县级行政区划");
+}
+
+// A full name wins over a short one wherever it is, as 吉林市 in 吉林省吉林市
+// whose short form 吉林 comes first; otherwise the earliest mention wins.
+fn find_division(address: &str, divisions: impl Iterator<Item = (&'static str, &'static str)>) -> Option<(&'static str, Confidence)> {
+  let mut best: Option<(usize, Confidence, &'static str)> = None;
+  for (code, name) in divisions {
+    if is_placeholder(name) {
+      continue;
+    }
+    let candidates = [Some((name, Confidence::High)), short_name(name).map(|short| (short, Confidence::Medium))];
+    for (text, confidence) in candidates.into_iter().flatten() {
+      if let Some(index) = address.find(text) {
+        let better = match best {
+          Some((best_index, best_confidence, _)) => confidence > best_confidence || (confidence == best_confidence && index < best_index),
+          None => true
+        };
+        if better {
+          best = Some((index, confidence, code));
+        }
+      }
+    }
+  }
+  return best.map(|(_, confidence, code)| (code, confidence));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressRegion {
+  // 2-digit province and 4-digit prefecture keys of the bundled divisions.
+  pub province: Option<(&'static str, Confidence)>,
+  pub prefecture: Option<(&'static str, Confidence)>
+}
+
+// County level is not bundled, so an address resolves to its prefecture at
+// most. A prefecture is only looked for within the province the address
+// names; without one, a prefecture named by its short form alone is a weak
+// hint, as with 朝阳 in 北京市朝阳区.
+pub fn parse_address(address: &str) -> AddressRegion {
+  let address: String = address.split_whitespace().collect();
+  let province = find_division(&address, division::provinces());
+  let prefecture = match province {
+    Some((province_code, _)) => find_division(&address, division::prefectures().filter(|(code, _)| code.starts_with(province_code))),
+    None => find_division(&address, division::prefectures()).map(|(code, confidence)| (code, if confidence == Confidence::High { confidence } else { Confidence::Low }))
+  };
+  let province = province.or_else(|| prefecture.map(|(code, confidence)| (division::provinces().find(|(province_code, _)| code.starts_with(province_code)).unwrap().0, confidence)));
+  return AddressRegion { province, prefecture };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionMatch {
+  Same,
+  DifferentProvince,
+  DifferentPrefecture,
+  // The address names no known division or the code's region is unknown.
+  Unknown
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressCheck {
+  pub address: AddressRegion,
+  pub result: RegionMatch,
+  // How sure the parse is at the level the result was decided on.
+  pub confidence: Confidence
+}
+
+pub fn check_address(address: &str, code: &UsciCode) -> AddressCheck {
+  let parsed = parse_address(address);
+  let region = code.code03();
+  let unknown = AddressCheck { address: parsed, result: RegionMatch::Unknown, confidence: Confidence::Low };
+  let (province, province_confidence) = match parsed.province {
+    Some(province) if division::province(region).is_some() => province,
+    _ => return unknown
+  };
+  if !region.starts_with(province) {
+    return AddressCheck { address: parsed, result: RegionMatch::DifferentProvince, confidence: province_confidence };
+  }
+  let code_prefecture = division::prefecture(region).filter(|name| !is_placeholder(name));
+  match (parsed.prefecture, code_prefecture) {
+    (Some((prefecture, confidence)), Some(_)) if !region.starts_with(prefecture) => {
+      AddressCheck { address: parsed, result: RegionMatch::DifferentPrefecture, confidence }
+    }
+    (Some((_, confidence)), Some(_)) => AddressCheck { address: parsed, result: RegionMatch::Same, confidence },
+    _ => AddressCheck { address: parsed, result: RegionMatch::Same, confidence: province_confidence }
+  }
+}
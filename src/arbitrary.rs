@@ -0,0 +1,73 @@
+use std::str::FromStr;
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+use crate::invalid::{Defect, NearValid};
+use crate::space::CodeSpace;
+use crate::usci::{Code01, Code02, Code03, Code04, Code05, UsciCode, CODE_CHARS};
+
+// Codes are drawn by rank and exhausted input reads as zero, so a fuzzer
+// minimizing its input also moves toward the first code of the space.
+impl<'a> Arbitrary<'a> for UsciCode {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    let space = CodeSpace::new();
+    return Ok(space.unrank(u.int_in_range(0..=space.size() - 1)?).unwrap());
+  }
+
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    return u128::size_hint(depth);
+  }
+}
+
+impl<'a> Arbitrary<'a> for NearValid {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    let defect = Defect::arbitrary(u)?;
+    let original = UsciCode::arbitrary(u)?;
+    let seed = u64::arbitrary(u)?;
+    return NearValid::new(original, defect, seed).ok_or(Error::IncorrectFormat);
+  }
+}
+
+impl<'a> Arbitrary<'a> for Defect {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    return u.choose(&Defect::ALL).copied();
+  }
+}
+
+impl<'a> Arbitrary<'a> for Code01 {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    return u.choose(&Code01::ALL).copied();
+  }
+}
+
+impl<'a> Arbitrary<'a> for Code02 {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    return u.choose(&Code02::ALL).copied();
+  }
+}
+
+impl<'a> Arbitrary<'a> for Code03 {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    let index = u.int_in_range(0..=999_999u32)?;
+    return Ok(Code03::new(&format!("{:06}", index)).unwrap());
+  }
+}
+
+impl<'a> Arbitrary<'a> for Code04 {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    return Ok(Code04::from_index(u.int_in_range(0..=CodeSpace::org_codes() - 1)?));
+  }
+}
+
+impl<'a> Arbitrary<'a> for Code05 {
+
+  fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+    let ch = u.choose(&CODE_CHARS)?;
+    return Ok(Code05::from_str(ch.encode_utf8(&mut [0; 4])).unwrap());
+  }
+}
@@ -0,0 +1,164 @@
+use crate::common::{UsciError, UsciResult};
+
+const INVALID: u8 = u8::MAX;
+
+const DIGITS: &[u8] = b"0123456789";
+const DIGITS_X: &[u8] = b"0123456789X";
+pub(crate) const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const USCI_CHARS: &[u8] = b"0123456789ABCDEFGHJKLMNPQRTUWXY";
+const USCI_WEIGHT: &[usize] = &[1, 3, 9, 27, 19, 26, 16, 17, 20, 29, 25, 13, 8, 24, 10, 30, 28];
+const ORG_CODE_WEIGHT: &[usize] = &[3, 7, 9, 10, 5, 8, 4, 2];
+
+// GB 32100: the 18th char of a usci code.
+pub const GB_32100: Checksum = Checksum::weighted(USCI_CHARS, USCI_CHARS, 31, USCI_WEIGHT);
+// GB 11714: the 9th char of an organization code.
+pub const GB_11714: Checksum = Checksum::weighted(ALPHANUMERIC, DIGITS_X, 11, ORG_CODE_WEIGHT);
+// ISO 7064 MOD 11-2: the 18th char of a resident identity card number.
+pub const MOD_11_2: Checksum = Checksum::pure(DIGITS, DIGITS_X, 11, 2, 1);
+// ISO 7064 MOD 11,10: the 15th digit of a registration number.
+pub const MOD_11_10: Checksum = Checksum::hybrid(DIGITS, DIGITS, 10);
+// ISO 7064 MOD 97-10: the last two digits of a LEI, after letters are expanded to digits.
+pub const MOD_97_10: Checksum = Checksum::pure(DIGITS, DIGITS, 97, 10, 2);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Algorithm {
+  // sum(value * weight) mod M, one weight per body char
+  Weighted(&'static [usize]),
+  // ISO 7064 pure system with the given radix
+  Pure(usize),
+  // ISO 7064 hybrid system MOD M+1,M
+  Hybrid
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Checksum {
+  values: [u8; 128],
+  check_chars: &'static [u8],
+  modulus: usize,
+  check_len: usize,
+  algorithm: Algorithm
+}
+
+impl Checksum {
+
+  const fn new(alphabet: &'static [u8], check_chars: &'static [u8], modulus: usize, check_len: usize, algorithm: Algorithm) -> Checksum {
+    let mut values = [INVALID; 128];
+    let mut index = 0;
+    while index < alphabet.len() {
+      values[alphabet[index] as usize] = index as u8;
+      index += 1;
+    }
+    return Checksum { values, check_chars, modulus, check_len, algorithm };
+  }
+
+  pub const fn weighted(alphabet: &'static [u8], check_chars: &'static [u8], modulus: usize, weights: &'static [usize]) -> Checksum {
+    return Checksum::new(alphabet, check_chars, modulus, 1, Algorithm::Weighted(weights));
+  }
+
+  pub const fn pure(alphabet: &'static [u8], check_chars: &'static [u8], modulus: usize, radix: usize, check_len: usize) -> Checksum {
+    return Checksum::new(alphabet, check_chars, modulus, check_len, Algorithm::Pure(radix));
+  }
+
+  pub const fn hybrid(alphabet: &'static [u8], check_chars: &'static [u8], modulus: usize) -> Checksum {
+    return Checksum::new(alphabet, check_chars, modulus, 1, Algorithm::Hybrid);
+  }
+
+  pub fn check_len(&self) -> usize {
+    return self.check_len;
+  }
+
+  pub fn modulus(&self) -> usize {
+    return self.modulus;
+  }
+
+  pub fn weights(&self) -> Option<&'static [usize]> {
+    match self.algorithm {
+      Algorithm::Weighted(weights) => Some(weights),
+      _ => None
+    }
+  }
+
+  pub fn value_of(&self, byte: u8) -> Option<usize> {
+    match self.values.get(byte as usize) {
+      Some(&value) if value != INVALID => Some(value as usize),
+      _ => None
+    }
+  }
+
+  pub fn is_valid_char(&self, ch: char) -> bool {
+    return ch.is_ascii() && self.value_of(ch as u8).is_some();
+  }
+
+  fn char_value(&self, body: &[u8], index: usize) -> UsciResult<usize> {
+    match self.value_of(body[index]) {
+      Some(value) => Ok(value),
+      None => Err(UsciError::CommonError(format!("Calculate Weight Error: {} {}", index, body[index] as char)))
+    }
+  }
+
+  pub fn compute_value(&self, body: &[u8]) -> UsciResult<usize> {
+    let modulus = self.modulus;
+    match self.algorithm {
+      Algorithm::Weighted(weights) => {
+        if body.len() != weights.len() {
+          return Err(UsciError::CommonError(format!("Checksum Error: expect {} chars, got {}", weights.len(), body.len())));
+        }
+        let mut sum = 0;
+        for (index, weight) in weights.iter().enumerate() {
+          sum += self.char_value(body, index)? * weight;
+        }
+        return Ok((modulus - sum % modulus) % modulus);
+      }
+      Algorithm::Pure(radix) => {
+        let mut product = 0;
+        for index in 0..body.len() {
+          product = (product * radix + self.char_value(body, index)?) % modulus;
+        }
+        for _ in 0..self.check_len {
+          product = product * radix % modulus;
+        }
+        let value = modulus + 1 - product;
+        return Ok(if self.check_len == 1 { value % modulus } else { value });
+      }
+      Algorithm::Hybrid => {
+        let mut product = modulus;
+        for index in 0..body.len() {
+          let mut sum = (product + self.char_value(body, index)?) % modulus;
+          if sum == 0 {
+            sum = modulus;
+          }
+          product = sum * 2 % (modulus + 1);
+        }
+        return Ok((modulus + 1 - product) % modulus);
+      }
+    }
+  }
+
+  pub fn compute(&self, body: &str) -> UsciResult<String> {
+    let mut value = self.compute_value(body.as_bytes())?;
+    let radix = self.check_chars.len();
+    let mut check = vec![0u8; self.check_len];
+    for byte in check.iter_mut().rev() {
+      *byte = self.check_chars[value % radix];
+      value /= radix;
+    }
+    return Ok(String::from_utf8(check).unwrap());
+  }
+
+  pub fn verify(&self, code: &str) -> bool {
+    if code.len() <= self.check_len || !code.is_ascii() {
+      return false;
+    }
+    let (body, check) = code.split_at(code.len() - self.check_len);
+    match self.compute(body) {
+      Ok(expected) => expected == check,
+      Err(_) => false
+    }
+  }
+
+  pub fn complete(&self, body: &str) -> UsciResult<String> {
+    let check = self.compute(body)?;
+    return Ok(format!("{}{}", body, check));
+  }
+
+}
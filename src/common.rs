@@ -1,11 +1,13 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::io;
+use crate::homoglyph::SpoofedChar;
 
 #[derive(Debug)]
 pub enum UsciError {
   IOError(io::Error),
-  CommonError(String)
+  CommonError(String),
+  SpoofedCharacter(Vec<SpoofedChar>)
 }
 
 impl From<io::Error> for UsciError {
@@ -21,6 +23,10 @@ impl Display for UsciError {
     match self {
       UsciError::IOError(ref err) => write!(f, "IO Error: {}", err),
       UsciError::CommonError(ref message) => write!(f, "Error: {}", message),
+      UsciError::SpoofedCharacter(ref spoofed) => {
+        let spoofed: Vec<String> = spoofed.iter().map(|item| item.to_string()).collect();
+        write!(f, "Spoofed Character: {}", spoofed.join(", "))
+      }
     }
   }
 }
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::usci::{Code01, UsciCode};
+
+const DEFAULT_CONFUSIONS: [(char, char); 7] = [('8', 'B'), ('0', 'D'), ('5', 'S'), ('2', 'Z'), ('1', 'L'), ('0', 'O'), ('1', 'I')];
+
+// Weight of reading `observed` when the printed char was `actual`.
+#[derive(Debug, Clone)]
+pub struct ConfusionMatrix {
+  weights: HashMap<(char, char), f64>
+}
+
+impl ConfusionMatrix {
+
+  pub fn new() -> Self {
+    return ConfusionMatrix { weights: HashMap::new() };
+  }
+
+  pub fn add(&mut self, observed: char, actual: char, weight: f64) {
+    self.weights.insert((observed, actual), weight);
+  }
+
+  pub fn add_pair(&mut self, left: char, right: char, weight: f64) {
+    self.add(left, right, weight);
+    self.add(right, left, weight);
+  }
+
+  pub fn get(&self, observed: char, actual: char) -> f64 {
+    return self.weights.get(&(observed, actual)).copied().unwrap_or(0.0);
+  }
+
+  // One "observed actual weight" triple per line, # starts a comment.
+  pub fn from_reader<R: BufRead>(reader: R) -> UsciResult<Self> {
+    let mut matrix = ConfusionMatrix::new();
+    for line in reader.lines() {
+      let line = line?;
+      let content = line.split('#').next().unwrap().trim();
+      if content.is_empty() {
+        continue;
+      }
+      let fields: Vec<&str> = content.split_whitespace().collect();
+      let parsed = match fields.as_slice() {
+        [observed, actual, weight] => {
+          match (single_char(observed), single_char(actual), weight.parse::<f64>()) {
+            (Some(observed), Some(actual), Ok(weight)) if weight.is_finite() && weight >= 0.0 => Some((observed, actual, weight)),
+            _ => None
+          }
+        }
+        _ => None
+      };
+      match parsed {
+        Some((observed, actual, weight)) => matrix.add(observed, actual, weight),
+        None => return Err(UsciError::CommonError(format!("Parse Confusion Error: {}", line)))
+      }
+    }
+    return Ok(matrix);
+  }
+
+  fn alternatives(&self, observed: char) -> impl Iterator<Item = (char, f64)> + '_ {
+    return self.weights.iter()
+      .filter(move |((from, to), &weight)| *from == observed && *to != observed && weight > 0.0)
+      .map(|((_, to), &weight)| (*to, weight));
+  }
+
+}
+
+impl Default for ConfusionMatrix {
+
+  fn default() -> Self {
+    let mut matrix = ConfusionMatrix::new();
+    for (left, right) in DEFAULT_CONFUSIONS.iter() {
+      matrix.add_pair(*left, *right, 1.0);
+    }
+    return matrix;
+  }
+}
+
+fn single_char(text: &str) -> Option<char> {
+  let mut chars = text.chars();
+  match (chars.next(), chars.next()) {
+    (Some(ch), None) => Some(ch),
+    _ => None
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Correction {
+  pub code: UsciCode,
+  pub score: f64,
+  // (index, observed, replacement) for every changed char; a transposition
+  // shows up as the two chars it swapped
+  pub edits: Vec<(usize, char, char)>
+}
+
+#[derive(Debug, Clone)]
+pub struct Corrector {
+  matrix: ConfusionMatrix,
+  max_edits: usize,
+  default_confidence: f64
+}
+
+impl Corrector {
+
+  pub fn new(matrix: ConfusionMatrix) -> Self {
+    return Corrector { matrix, max_edits: 2, default_confidence: 0.9 };
+  }
+
+  pub fn max_edits(mut self, max_edits: usize) -> Self {
+    self.max_edits = max_edits;
+    return self;
+  }
+
+  pub fn default_confidence(mut self, confidence: f64) -> Self {
+    self.default_confidence = confidence;
+    return self;
+  }
+
+  fn is_valid_at(index: usize, ch: char) -> bool {
+    match index {
+      0 => Code01::from_char(ch).is_ok(),
+      2..=7 => ch.is_ascii_digit(),
+      _ => checksum::GB_32100.is_valid_char(ch)
+    }
+  }
+
+  // The observed char keeps its confidence, the rest of the probability is
+  // shared by its confusable alternatives in proportion to their weights.
+  fn options(&self, index: usize, observed: char, confidence: f64) -> Vec<(char, f64)> {
+    let alternatives: Vec<(char, f64)> = self.matrix.alternatives(observed)
+      .filter(|(actual, _)| Corrector::is_valid_at(index, *actual))
+      .collect();
+    let total: f64 = alternatives.iter().map(|(_, weight)| weight).sum();
+    let mut options = Vec::with_capacity(alternatives.len() + 1);
+    let mut remaining = 1.0;
+    if Corrector::is_valid_at(index, observed) {
+      options.push((observed, confidence));
+      remaining = 1.0 - confidence;
+    }
+    for (actual, weight) in alternatives {
+      options.push((actual, remaining * weight / total));
+    }
+    return options;
+  }
+
+  pub fn correct(&self, observed: &str, confidences: Option<&[f64]>) -> UsciResult<Vec<Correction>> {
+    let observed: Vec<char> = observed.chars().map(|ch| ch.to_ascii_uppercase()).collect();
+    if observed.len() != 18 {
+      return Err(UsciError::CommonError(format!("Parse Code Error: {}", observed.iter().collect::<String>())));
+    }
+    if let Some(confidences) = confidences {
+      if confidences.len() != 18 || confidences.iter().any(|confidence| !(0.0..=1.0).contains(confidence)) {
+        return Err(UsciError::CommonError("Parse Confidence Error: expect 18 values between 0 and 1".to_string()));
+      }
+    }
+    let confidences: Vec<f64> = (0..18)
+      .map(|index| confidences.map(|confidences| confidences[index]).unwrap_or(self.default_confidence))
+      .collect();
+    let options: Vec<Vec<(char, f64)>> = observed.iter().enumerate()
+      .map(|(index, &ch)| self.options(index, ch, confidences[index]))
+      .collect();
+    let swaps: Vec<f64> = (0..17)
+      .map(|index| Corrector::swap(&observed, &confidences, index))
+      .collect();
+    let mut corrections = Vec::new();
+    let mut current = [0u8; 18];
+    self.search(&observed, &options, &swaps, 0, 0, 1.0, &mut current, &mut corrections);
+    corrections.sort_by(|left, right| right.score.total_cmp(&left.score).then(left.code.cmp(&right.code)));
+    // A swap and two substitutions can reach the same code; keep the likelier.
+    let mut seen = HashSet::new();
+    corrections.retain(|correction| seen.insert(correction.code));
+    return Ok(corrections);
+  }
+
+  // Reading two neighbours in the wrong order is one edit, as likely as both
+  // chars being misread; swapping equal or misplaced chars is not an option.
+  fn swap(observed: &[char], confidences: &[f64], index: usize) -> f64 {
+    let (left, right) = (observed[index], observed[index + 1]);
+    if left == right || !Corrector::is_valid_at(index, right) || !Corrector::is_valid_at(index + 1, left) {
+      return 0.0;
+    }
+    return (1.0 - confidences[index]) * (1.0 - confidences[index + 1]);
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn search(&self, observed: &[char], options: &[Vec<(char, f64)>], swaps: &[f64], index: usize, edits: usize, score: f64, current: &mut [u8; 18], corrections: &mut Vec<Correction>) {
+    if index == 18 {
+      if let Ok(code) = UsciCode::from_bytes(current) {
+        let edits = observed.iter().zip(current.iter()).enumerate()
+          .filter(|(_, (&from, &to))| from != to as char)
+          .map(|(index, (&from, &to))| (index, from, to as char))
+          .collect();
+        corrections.push(Correction { code, score, edits });
+      }
+      return;
+    }
+    for &(actual, probability) in options[index].iter() {
+      let edit = if actual == observed[index] { 0 } else { 1 };
+      if edits + edit > self.max_edits || probability <= 0.0 {
+        continue;
+      }
+      current[index] = actual as u8;
+      self.search(observed, options, swaps, index + 1, edits + edit, score * probability, current, corrections);
+    }
+    if index < 17 && edits < self.max_edits && swaps[index] > 0.0 {
+      current[index] = observed[index + 1] as u8;
+      current[index + 1] = observed[index] as u8;
+      self.search(observed, options, swaps, index + 2, edits + 1, score * swaps[index], current, corrections);
+    }
+  }
+
+}
+
+impl Default for Corrector {
+
+  fn default() -> Self {
+    return Corrector::new(ConfusionMatrix::default());
+  }
+}
@@ -0,0 +1,37 @@
+use std::str::FromStr;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::{Binary, Text};
+use crate::usci::{PackedUsciCode, UsciCode};
+
+// A UsciCode column is Text and a PackedUsciCode column Binary with the nine
+// packed bytes, on every backend. Both are parsed again on every read, so a
+// row holding an invalid code fails to load.
+impl<DB: Backend> ToSql<Text, DB> for UsciCode where str: ToSql<Text, DB> {
+
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    return self.as_str().to_sql(out);
+  }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for UsciCode where String: FromSql<Text, DB> {
+
+  fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+    return Ok(UsciCode::from_str(&String::from_sql(bytes)?)?);
+  }
+}
+
+impl<DB: Backend> ToSql<Binary, DB> for PackedUsciCode where [u8]: ToSql<Binary, DB> {
+
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    return self.as_bytes()[..].to_sql(out);
+  }
+}
+
+impl<DB: Backend> FromSql<Binary, DB> for PackedUsciCode where Vec<u8>: FromSql<Binary, DB> {
+
+  fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+    return Ok(PackedUsciCode::from_bytes(&Vec::<u8>::from_sql(bytes)?)?);
+  }
+}
@@ -0,0 +1,96 @@
+use fake::{Dummy, Faker};
+use rand::Rng;
+use crate::invalid::Defect;
+use crate::realistic::{RealisticGenerator, Weights};
+use crate::record::Record;
+use crate::space::CodeSpace;
+use crate::usci::{Code01, Code02, Code03, Code04, UsciCode};
+
+// Faker draws as UsciCode::from_random does; a CodeSpace or a
+// RealisticGenerator is the config for the constrained and the realistic
+// variants. Only the given rng is used, so seeded fakes repeat.
+impl Dummy<Faker> for UsciCode {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+    return UsciCode::from_random(rng);
+  }
+}
+
+impl Dummy<CodeSpace> for UsciCode {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(space: &CodeSpace, rng: &mut R) -> Self {
+    return space.sample(rng);
+  }
+}
+
+impl Dummy<RealisticGenerator> for UsciCode {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(generator: &RealisticGenerator, rng: &mut R) -> Self {
+    return generator.generate(rng);
+  }
+}
+
+impl Dummy<Faker> for Record {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+    let code = UsciCode::dummy_with_rng(config, rng);
+    return Record::from_code(code, rng);
+  }
+}
+
+// The address needs a known region, so the code comes from the realistic
+// generator narrowed to the space; a space it cannot cover, such as one
+// without a known prefecture, falls back to a plain sample of the space.
+impl Dummy<CodeSpace> for Record {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(space: &CodeSpace, rng: &mut R) -> Self {
+    let code = match RealisticGenerator::within(&Weights::default(), space) {
+      Ok(generator) => generator.generate(rng),
+      Err(_) => space.sample(rng)
+    };
+    return Record::from_code(code, rng);
+  }
+}
+
+impl Dummy<RealisticGenerator> for Record {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(generator: &RealisticGenerator, rng: &mut R) -> Self {
+    let code = generator.generate(rng);
+    return Record::from_code(code, rng);
+  }
+}
+
+impl Dummy<Faker> for Code01 {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+    return Code01::from_random(rng);
+  }
+}
+
+impl Dummy<Faker> for Code02 {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+    return Code02::ALL[rng.gen_range(0..Code02::ALL.len())];
+  }
+}
+
+impl Dummy<Faker> for Code03 {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+    return Code03::from_random(rng);
+  }
+}
+
+impl Dummy<Faker> for Code04 {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+    return Code04::from_random(rng);
+  }
+}
+
+impl Dummy<Faker> for Defect {
+
+  fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+    return Defect::ALL[rng.gen_range(0..Defect::ALL.len())];
+  }
+}
@@ -0,0 +1,113 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpoofedChar {
+  pub index: usize,
+  pub found: char,
+  pub looks_like: char
+}
+
+impl SpoofedChar {
+
+  pub fn code_point(&self) -> u32 {
+    return self.found as u32;
+  }
+
+}
+
+impl Display for SpoofedChar {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: U+{:04X} '{}' looks like '{}'", self.index + 1, self.code_point(), self.found, self.looks_like)
+  }
+}
+
+// Full-width forms and the Cyrillic/Greek capitals that render like the
+// ASCII digits and letters a code is made of; full-width lower case is taken
+// for the capital. I, O, S and Z never appear in a code, so their look-alikes
+// are taken for the digits 1, 0, 5 and 2.
+pub fn look_alike(ch: char) -> Option<char> {
+  match ch {
+    'Ｉ' | 'ｉ' => Some('1'),
+    'Ｏ' | 'ｏ' => Some('0'),
+    'Ｓ' | 'ｓ' => Some('5'),
+    'Ｚ' | 'ｚ' => Some('2'),
+    '\u{FF10}'..='\u{FF19}' | '\u{FF21}'..='\u{FF3A}' => char::from_u32(ch as u32 - 0xFF10 + '0' as u32),
+    '\u{FF41}'..='\u{FF5A}' => char::from_u32(ch as u32 - 0xFF41 + 'A' as u32),
+    '\u{3007}' => Some('0'),
+    'А' | 'Α' => Some('A'),
+    'В' | 'Β' => Some('B'),
+    'С' | 'Ϲ' => Some('C'),
+    'Е' | 'Ε' => Some('E'),
+    'Н' | 'Η' => Some('H'),
+    'І' | 'Ι' => Some('1'),
+    'Ј' => Some('J'),
+    'К' | 'Κ' => Some('K'),
+    'М' | 'Μ' => Some('M'),
+    'Ν' => Some('N'),
+    'О' | 'Ο' => Some('0'),
+    'Р' | 'Ρ' => Some('P'),
+    'Ѕ' => Some('5'),
+    'Т' | 'Τ' => Some('T'),
+    'У' | 'Υ' | 'Ү' => Some('Y'),
+    'Х' | 'Χ' => Some('X'),
+    'З' => Some('3'),
+    'Ζ' => Some('2'),
+    _ => None
+  }
+}
+
+pub fn find_spoofed(code: &str) -> Vec<SpoofedChar> {
+  return code.chars().enumerate()
+    .filter_map(|(index, found)| look_alike(found).map(|looks_like| SpoofedChar { index, found, looks_like }))
+    .collect();
+}
+
+pub fn normalize(code: &str) -> (String, Vec<SpoofedChar>) {
+  let normalized = code.chars().map(|ch| look_alike(ch).unwrap_or(ch)).collect();
+  return (normalized, find_spoofed(code));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn full_width_maps_to_ascii() {
+    assert_eq!(look_alike('０'), Some('0'));
+    assert_eq!(look_alike('９'), Some('9'));
+    assert_eq!(look_alike('Ａ'), Some('A'));
+    assert_eq!(look_alike('Ｙ'), Some('Y'));
+    assert_eq!(look_alike('ａ'), Some('A'));
+    assert_eq!(look_alike('ｙ'), Some('Y'));
+  }
+
+  #[test]
+  fn full_width_letters_outside_the_code_map_to_digits() {
+    for (chars, digit) in [("Ｉｉ", '1'), ("Ｏｏ", '0'), ("Ｓｓ", '5'), ("Ｚｚ", '2')] {
+      for ch in chars.chars() {
+        assert_eq!(look_alike(ch), Some(digit), "{}", ch);
+      }
+    }
+  }
+
+  #[test]
+  fn cyrillic_and_greek_map_to_ascii() {
+    assert_eq!(look_alike('А'), Some('A'));
+    assert_eq!(look_alike('Α'), Some('A'));
+    assert_eq!(look_alike('О'), Some('0'));
+    assert_eq!(look_alike('З'), Some('3'));
+    assert_eq!(look_alike('\u{3007}'), Some('0'));
+    assert_eq!(look_alike('A'), None);
+    assert_eq!(look_alike('中'), None);
+  }
+
+  #[test]
+  fn normalize_reports_every_replacement() {
+    let (normalized, spoofed) = normalize("9135０100МＯＯＯ100Y43");
+    assert_eq!(normalized, "91350100M000100Y43");
+    assert_eq!(spoofed.iter().map(|spoofed| spoofed.index).collect::<Vec<_>>(), vec![4, 8, 9, 10, 11]);
+    assert_eq!(spoofed[0].to_string(), "5: U+FF10 '０' looks like '0'");
+    assert!(find_spoofed("91350100M000100Y43").is_empty());
+  }
+}
@@ -0,0 +1,173 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use rand::Rng;
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::division;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gender {
+  Male,
+  Female
+}
+
+impl Gender {
+
+  pub fn get_type(&self) -> &'static str {
+    match self {
+      Gender::Male => "男",
+      Gender::Female => "女"
+    }
+  }
+
+}
+
+fn is_leap_year(year: u32) -> bool {
+  return (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400);
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+  match month {
+    1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+    4 | 6 | 9 | 11 => 30,
+    2 if is_leap_year(year) => 29,
+    2 => 28,
+    _ => 0
+  }
+}
+
+// Today in UTC as (year, month, day), from the days since 1970-01-01 by
+// the civil calendar algorithm of Howard Hinnant.
+fn today() -> (u32, u32, u32) {
+  let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0);
+  let days = seconds / 86_400 + 719_468;
+  let (era, day_of_era) = (days / 146_097, days % 146_097);
+  let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+  let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+  return (year as u32, month as u32, day as u32);
+}
+
+// The birth date is personal data, so it is hidden unless asked for; this
+// also applies to numbers that failed to parse.
+pub fn mask(code: &str) -> String {
+  if code.len() < 14 || !code.is_ascii() {
+    return code.to_string();
+  }
+  return format!("{}********{}", &code[..6], &code[14..]);
+}
+
+// GB 11643: 6-digit region, 8-digit birth date, 3-digit sequence whose
+// parity gives the gender, and a MOD 11-2 check char.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResidentId {
+  code: [u8; 18]
+}
+
+impl ResidentId {
+
+  pub fn code_type() -> &'static str {
+    return "居民身份证号码";
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> UsciResult<Self> {
+    let code: [u8; 18] = match bytes.try_into() {
+      Ok(code) => code,
+      Err(_) => return Err(UsciError::CommonError(format!("Parse ID Error: {}", String::from_utf8_lossy(bytes))))
+    };
+    if !code[..17].iter().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse ID Error: {}", String::from_utf8_lossy(bytes))));
+    }
+    // Numbers are never reissued when divisions change, so only the province
+    // has to exist; an abolished prefecture or county is still a valid ID.
+    let region = std::str::from_utf8(&code[..6]).unwrap();
+    if division::province(region).is_none() {
+      return Err(UsciError::CommonError(format!("Parse ID Region Error: {}", region)));
+    }
+    let id = ResidentId { code };
+    let (year, month, day) = id.birth_date();
+    if year < 1900 || day == 0 || day > days_in_month(year, month) || (year, month, day) > today() {
+      return Err(UsciError::CommonError(format!("Parse ID Birth Date Error: {}", &id.as_str()[6..14])));
+    }
+    let check = checksum::MOD_11_2.compute(&id.as_str()[..17])?;
+    if check.as_bytes()[0] != code[17] {
+      return Err(UsciError::CommonError(format!("Verify ID Check Error: {}", check)));
+    }
+    return Ok(id);
+  }
+
+  // First generation numbers have 15 digits: a 2-digit year and no check char.
+  pub fn from_legacy(code: &str) -> UsciResult<Self> {
+    if code.len() != 15 || !code.bytes().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse ID Error: {}", code)));
+    }
+    let body = format!("{}19{}", &code[..6], &code[6..]);
+    return ResidentId::from_str(&checksum::MOD_11_2.complete(&body)?);
+  }
+
+  pub fn from_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    let prefectures: Vec<&str> = division::prefectures().map(|(code, _)| code).collect();
+    let prefecture = prefectures[rng.gen_range(0..prefectures.len())];
+    let year = rng.gen_range(1950..2006);
+    let month = rng.gen_range(1..=12);
+    let day = rng.gen_range(1..=days_in_month(year, month));
+    let body = format!("{}{:02}{:04}{:02}{:02}{:03}", prefecture, rng.gen_range(1..20), year, month, day, rng.gen_range(0..1000));
+    return ResidentId::from_str(&checksum::MOD_11_2.complete(&body).unwrap()).unwrap();
+  }
+
+  pub fn region(&self) -> &str {
+    return &self.as_str()[..6];
+  }
+
+  pub fn birth_date(&self) -> (u32, u32, u32) {
+    let text = self.as_str();
+    return (text[6..10].parse().unwrap(), text[10..12].parse().unwrap(), text[12..14].parse().unwrap());
+  }
+
+  pub fn sequence(&self) -> &str {
+    return &self.as_str()[14..17];
+  }
+
+  pub fn gender(&self) -> Gender {
+    return if (self.code[16] - b'0') % 2 == 1 { Gender::Male } else { Gender::Female };
+  }
+
+  pub fn check(&self) -> char {
+    return self.code[17] as char;
+  }
+
+  pub fn as_str(&self) -> &str {
+    return std::str::from_utf8(&self.code).unwrap();
+  }
+
+  pub fn masked(&self) -> String {
+    return mask(self.as_str());
+  }
+
+}
+
+impl FromStr for ResidentId {
+  type Err = UsciError;
+
+  fn from_str(code: &str) -> UsciResult<Self> {
+    return ResidentId::from_bytes(code.to_ascii_uppercase().as_bytes());
+  }
+}
+
+impl Display for ResidentId {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.as_str())
+  }
+}
+
+impl AsRef<str> for ResidentId {
+
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
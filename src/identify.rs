@@ -0,0 +1,118 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::checksum;
+use crate::idcard::ResidentId;
+use crate::taxid::TaxpayerId;
+use crate::usci::UsciCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IdentifierKind {
+  Usci,
+  ResidentId,
+  TaxpayerId,
+  RegistrationNumber,
+  OrgCode
+}
+
+impl IdentifierKind {
+
+  pub fn get_code(&self) -> &'static str {
+    match self {
+      IdentifierKind::Usci => "usci",
+      IdentifierKind::ResidentId => "id-card",
+      IdentifierKind::TaxpayerId => "tax-id",
+      IdentifierKind::RegistrationNumber => "reg-no",
+      IdentifierKind::OrgCode => "org-code"
+    }
+  }
+
+  pub fn get_type(&self) -> &'static str {
+    match self {
+      IdentifierKind::Usci => "统一社会信用代码",
+      IdentifierKind::ResidentId => "居民身份证号码",
+      IdentifierKind::TaxpayerId => "纳税人识别号",
+      IdentifierKind::RegistrationNumber => "工商注册号",
+      IdentifierKind::OrgCode => "组织机构代码"
+    }
+  }
+
+}
+
+impl Display for IdentifierKind {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.get_code())
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Identification {
+  pub kind: IdentifierKind,
+  pub valid: bool
+}
+
+fn is_digits(text: &str) -> bool {
+  return text.bytes().all(|byte| byte.is_ascii_digit());
+}
+
+fn is_org_code(text: &str) -> bool {
+  return checksum::GB_11714.verify(text);
+}
+
+fn is_resident_id(text: &str) -> bool {
+  match text.len() {
+    18 => ResidentId::from_str(text).is_ok(),
+    15 => ResidentId::from_legacy(text).is_ok(),
+    _ => false
+  }
+}
+
+fn is_taxpayer_id(text: &str) -> bool {
+  return TaxpayerId::from_str(text).is_ok();
+}
+
+// Every identifier kind the text could be, valid ones first and otherwise
+// in the order the shape makes them likely.
+pub fn identify(text: &str) -> Vec<Identification> {
+  let text = text.trim().to_ascii_uppercase();
+  let text = text.as_str();
+  let mut found = Vec::new();
+  if !text.is_ascii() {
+    return found;
+  }
+  let mut push = |kind: IdentifierKind, valid: bool| found.push(Identification { kind, valid });
+  match text.len() {
+    18 if is_digits(&text[..17]) => {
+      let usci = UsciCode::from_str(text).is_ok();
+      let resident_id = is_resident_id(text);
+      push(IdentifierKind::ResidentId, resident_id);
+      push(IdentifierKind::Usci, usci);
+      push(IdentifierKind::TaxpayerId, is_taxpayer_id(text));
+    }
+    18 => {
+      let usci = UsciCode::from_str(text).is_ok();
+      push(IdentifierKind::Usci, usci);
+      push(IdentifierKind::TaxpayerId, is_taxpayer_id(text));
+    }
+    15 if is_digits(text) => {
+      push(IdentifierKind::RegistrationNumber, checksum::MOD_11_10.verify(text));
+      push(IdentifierKind::TaxpayerId, is_taxpayer_id(text));
+      push(IdentifierKind::ResidentId, is_resident_id(text));
+    }
+    15 if is_digits(&text[..6]) => {
+      push(IdentifierKind::TaxpayerId, is_taxpayer_id(text));
+    }
+    20 | 17 if is_digits(&text[..text.len() - 3]) => {
+      push(IdentifierKind::TaxpayerId, is_taxpayer_id(text));
+    }
+    10 if text.as_bytes()[8] == b'-' => {
+      push(IdentifierKind::OrgCode, is_org_code(&text.replace('-', "")));
+    }
+    9 => {
+      push(IdentifierKind::OrgCode, is_org_code(text));
+    }
+    _ => {}
+  }
+  found.sort_by_key(|identification| !identification.valid);
+  return found;
+}
@@ -0,0 +1,173 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::usci::{Code01, Code02, Code03, Code04, UsciCode, CODE_CHARS};
+
+// Letters that never appear in a code.
+const FOREIGN_CHARS: [char; 5] = ['I', 'O', 'S', 'V', 'Z'];
+
+// The one way a corrupted code fails to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Defect {
+  Length,
+  Code01,
+  Code02,
+  Code03,
+  Code04,
+  Check,
+  Transposed
+}
+
+impl Defect {
+
+  pub const ALL: [Defect; 7] = [Defect::Length, Defect::Code01, Defect::Code02, Defect::Code03, Defect::Code04, Defect::Check, Defect::Transposed];
+
+  pub fn get_code(&self) -> &'static str {
+    match self {
+      Defect::Length => "length",
+      Defect::Code01 => "code01",
+      Defect::Code02 => "code02",
+      Defect::Code03 => "code03",
+      Defect::Code04 => "code04",
+      Defect::Check => "check",
+      Defect::Transposed => "transposed"
+    }
+  }
+
+  pub fn get_type(&self) -> &'static str {
+    match self {
+      Defect::Length => "长度错误",
+      Defect::Code01 => "登记管理部门代码错误",
+      Defect::Code02 => "机构类别与登记管理部门不符",
+      Defect::Code03 => "行政区划码含非数字字符",
+      Defect::Code04 => "组织机构代码含非法字符",
+      Defect::Check => "校验码错误",
+      Defect::Transposed => "相邻字符颠倒"
+    }
+  }
+
+  // The first defect a string shows, checked in parsing order; None when it
+  // is a valid code. A transposition only shows as a wrong check char.
+  pub fn diagnose(code: &str) -> Option<Defect> {
+    let bytes = code.as_bytes();
+    if bytes.len() != 18 {
+      return Some(Defect::Length);
+    }
+    let code01 = match Code01::from_char(bytes[0] as char) {
+      Ok(code01) => code01,
+      Err(_) => return Some(Defect::Code01)
+    };
+    if Code02::from_char(&code01, bytes[1] as char).is_err() {
+      return Some(Defect::Code02);
+    }
+    if Code03::from_bytes(&bytes[2..8]).is_err() {
+      return Some(Defect::Code03);
+    }
+    if Code04::from_bytes(&bytes[8..17]).is_err() {
+      return Some(Defect::Code04);
+    }
+    if UsciCode::from_bytes(bytes).is_err() {
+      return Some(Defect::Check);
+    }
+    return None;
+  }
+
+  // Breaks a valid code in this way only: a body that can still be checked
+  // gets a fresh check char, so the check char is only wrong for Check and
+  // Transposed. None when the code offers no place for the defect, as for a
+  // transposition in a code without two differing neighbours.
+  pub fn corrupt<R: Rng + ?Sized>(&self, code: &UsciCode, rng: &mut R) -> Option<String> {
+    let mut chars: Vec<char> = code.as_str().chars().collect();
+    match self {
+      Defect::Length => {
+        if rng.gen_bool(0.5) {
+          chars.remove(rng.gen_range(0..chars.len()));
+        } else {
+          chars.insert(rng.gen_range(0..=chars.len()), *CODE_CHARS.choose(rng).unwrap());
+        }
+        return Some(chars.into_iter().collect());
+      }
+      Defect::Code01 => {
+        let options: Vec<char> = CODE_CHARS.iter().copied().filter(|&ch| Code01::from_char(ch).is_err()).collect();
+        chars[0] = *options.choose(rng).unwrap();
+        return Some(rechecked(&chars));
+      }
+      Defect::Code02 => {
+        let options: Vec<char> = CODE_CHARS.iter().copied().filter(|&ch| Code02::from_char(&code.code01(), ch).is_err()).collect();
+        chars[1] = *options.choose(rng).unwrap();
+        return Some(rechecked(&chars));
+      }
+      Defect::Code03 => {
+        chars[rng.gen_range(2..8)] = *CODE_CHARS[10..].choose(rng).unwrap();
+        return Some(rechecked(&chars));
+      }
+      Defect::Code04 => {
+        chars[rng.gen_range(8..17)] = *FOREIGN_CHARS.choose(rng).unwrap();
+        return Some(chars.into_iter().collect());
+      }
+      Defect::Check => {
+        let options: Vec<char> = CODE_CHARS.iter().copied().filter(|&ch| ch != chars[17]).collect();
+        chars[17] = *options.choose(rng).unwrap();
+        return Some(chars.into_iter().collect());
+      }
+      Defect::Transposed => {
+        // Pairs within Code03 or within Code04, so every char stays valid
+        // where it lands; the weights differ, so the check char catches it.
+        // The first differing pair from a drawn start, so one seed keeps to
+        // one place across codes that differ elsewhere.
+        let pairs: Vec<usize> = (2..16).filter(|&index| index != 7).collect();
+        let start = rng.gen_range(0..pairs.len());
+        let index = pairs.iter().cycle().skip(start).take(pairs.len()).find(|&&index| chars[index] != chars[index + 1])?;
+        chars.swap(*index, *index + 1);
+        return Some(chars.into_iter().collect());
+      }
+    }
+  }
+
+}
+
+// A valid code and the same code broken by exactly one defect, as drawn by
+// the property-testing integrations. The corruption is drawn from the seed,
+// so a shrunk original can be broken again in the same place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearValid {
+  pub original: UsciCode,
+  pub code: String,
+  pub defect: Defect,
+  pub seed: u64
+}
+
+impl NearValid {
+
+  pub fn new(original: UsciCode, defect: Defect, seed: u64) -> Option<Self> {
+    let code = defect.corrupt(&original, &mut StdRng::seed_from_u64(seed))?;
+    return Some(NearValid { original, code, defect, seed });
+  }
+
+}
+
+fn rechecked(chars: &[char]) -> String {
+  let body: String = chars[..17].iter().collect();
+  return checksum::GB_32100.complete(&body).unwrap();
+}
+
+impl FromStr for Defect {
+  type Err = UsciError;
+
+  fn from_str(code: &str) -> UsciResult<Self> {
+    return Defect::ALL.iter().copied()
+      .find(|defect| defect.get_code() == code)
+      .ok_or_else(|| UsciError::CommonError(format!("Parse Defect Error: {}", code)));
+  }
+}
+
+impl Display for Defect {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.get_code())
+  }
+}
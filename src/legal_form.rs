@@ -0,0 +1,120 @@
+use crate::usci::{Code02, UsciCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegalForm {
+  pub suffix: &'static str,
+  pub categories: &'static [Code02]
+}
+
+const ENTERPRISE: &[Code02] = &[Code02::L91];
+const SHOP: &[Code02] = &[Code02::L91, Code02::L92];
+const COOPERATIVE: &[Code02] = &[Code02::L93];
+const COLLECTIVE: &[Code02] = &[Code02::NN1, Code02::NN2, Code02::NN3, Code02::NN9];
+const SOCIAL_GROUP: &[Code02] = &[Code02::H51];
+const FOUNDATION: &[Code02] = &[Code02::H53];
+const SCHOOL: &[Code02] = &[Code02::D12, Code02::H52];
+const GOVERNMENT: &[Code02] = &[Code02::D11];
+const LAW_FIRM: &[Code02] = &[Code02::F31];
+const NOTARY: &[Code02] = &[Code02::F32];
+const LEGAL_SERVICE: &[Code02] = &[Code02::F33];
+const FORENSIC: &[Code02] = &[Code02::F34];
+const ARBITRATION: &[Code02] = &[Code02::F35];
+const TRADE_UNION: &[Code02] = &[Code02::K81];
+const TEMPLE: &[Code02] = &[Code02::J71];
+const SEMINARY: &[Code02] = &[Code02::J72];
+
+// Longer suffixes first, so 经济合作社 wins over 合作社 and 律师事务所
+// over 事务所.
+const LEGAL_FORMS: [LegalForm; 37] = [
+  LegalForm { suffix: "股份经济合作社", categories: COLLECTIVE },
+  LegalForm { suffix: "经济联合总社", categories: COLLECTIVE },
+  LegalForm { suffix: "经济合作社", categories: COLLECTIVE },
+  LegalForm { suffix: "经济联合社", categories: COLLECTIVE },
+  LegalForm { suffix: "农民专业合作社", categories: COOPERATIVE },
+  LegalForm { suffix: "专业合作社", categories: COOPERATIVE },
+  LegalForm { suffix: "合作社联合社", categories: COOPERATIVE },
+  LegalForm { suffix: "合作社", categories: COOPERATIVE },
+  LegalForm { suffix: "有限合伙", categories: ENTERPRISE },
+  LegalForm { suffix: "普通合伙", categories: ENTERPRISE },
+  LegalForm { suffix: "公司", categories: ENTERPRISE },
+  LegalForm { suffix: "集团", categories: ENTERPRISE },
+  LegalForm { suffix: "厂", categories: SHOP },
+  LegalForm { suffix: "律师事务所", categories: LAW_FIRM },
+  LegalForm { suffix: "事务所", categories: ENTERPRISE },
+  LegalForm { suffix: "公证处", categories: NOTARY },
+  LegalForm { suffix: "法律服务所", categories: LEGAL_SERVICE },
+  LegalForm { suffix: "司法鉴定所", categories: FORENSIC },
+  LegalForm { suffix: "司法鉴定中心", categories: FORENSIC },
+  LegalForm { suffix: "仲裁委员会", categories: ARBITRATION },
+  LegalForm { suffix: "工会委员会", categories: TRADE_UNION },
+  LegalForm { suffix: "工会", categories: TRADE_UNION },
+  LegalForm { suffix: "基金会", categories: FOUNDATION },
+  LegalForm { suffix: "协会", categories: SOCIAL_GROUP },
+  LegalForm { suffix: "学会", categories: SOCIAL_GROUP },
+  LegalForm { suffix: "商会", categories: SOCIAL_GROUP },
+  LegalForm { suffix: "联合会", categories: SOCIAL_GROUP },
+  LegalForm { suffix: "学校", categories: SCHOOL },
+  LegalForm { suffix: "幼儿园", categories: SCHOOL },
+  LegalForm { suffix: "人民政府", categories: GOVERNMENT },
+  LegalForm { suffix: "佛学院", categories: SEMINARY },
+  LegalForm { suffix: "神学院", categories: SEMINARY },
+  LegalForm { suffix: "清真寺", categories: TEMPLE },
+  LegalForm { suffix: "教堂", categories: TEMPLE },
+  LegalForm { suffix: "经营部", categories: SHOP },
+  LegalForm { suffix: "商行", categories: SHOP },
+  LegalForm { suffix: "店", categories: SHOP }
+];
+
+// A trailing (有限合伙) is the legal form itself, any other trailing
+// bracket such as a branch location is dropped.
+fn strip_brackets(name: &str) -> &str {
+  let name = name.trim();
+  for (open, close) in [('(', ')'), ('（', '）')] {
+    if let Some(inner) = name.strip_suffix(close) {
+      if let Some(index) = inner.rfind(open) {
+        let content = &inner[index + open.len_utf8()..];
+        if LEGAL_FORMS.iter().any(|form| content.ends_with(form.suffix)) {
+          return content;
+        }
+        return inner[..index].trim_end();
+      }
+    }
+  }
+  return name;
+}
+
+pub fn legal_form(name: &str) -> Option<&'static LegalForm> {
+  let name = strip_brackets(name);
+  return LEGAL_FORMS.iter().find(|form| name.ends_with(form.suffix));
+}
+
+pub fn suffixes(code02: &Code02) -> Vec<&'static str> {
+  return LEGAL_FORMS.iter().filter(|form| form.categories.contains(code02)).map(|form| form.suffix).collect();
+}
+
+pub fn category_label(code02: &Code02) -> String {
+  return format!("{} {}", code02, code02.get_type());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameCheck {
+  // None when the name ends in no known legal form.
+  pub legal_form: Option<&'static LegalForm>,
+  pub actual: Code02
+}
+
+impl NameCheck {
+
+  pub fn is_consistent(&self) -> bool {
+    return self.legal_form.is_none_or(|form| form.categories.contains(&self.actual));
+  }
+
+  pub fn expected(&self) -> &'static [Code02] {
+    return self.legal_form.map(|form| form.categories).unwrap_or(&[]);
+  }
+
+}
+
+pub fn check_name(name: &str, code: &UsciCode) -> NameCheck {
+  return NameCheck { legal_form: legal_form(name), actual: code.code02() };
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+use rand::Rng;
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::usci::UsciCode;
+
+// Letters count as 10..35 and are written out as two digits before the
+// MOD 97-10 check, so "5493" stays "5493" and "UK" becomes "3020".
+fn expand(body: &str) -> String {
+  let mut digits = String::with_capacity(body.len() * 2);
+  for ch in body.chars() {
+    match ch.to_digit(36) {
+      Some(value) => digits.push_str(&value.to_string()),
+      None => digits.push(ch)
+    }
+  }
+  return digits;
+}
+
+// ISO 17442: 4-char prefix of the issuing LOU, 14-char entity part and two
+// MOD 97-10 check digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Lei {
+  code: [u8; 20]
+}
+
+impl Lei {
+
+  pub fn code_type() -> &'static str {
+    return "全球法人识别编码";
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> UsciResult<Self> {
+    let code: [u8; 20] = match bytes.try_into() {
+      Ok(code) => code,
+      Err(_) => return Err(UsciError::CommonError(format!("Parse LEI Error: {}", String::from_utf8_lossy(bytes))))
+    };
+    if !code[..18].iter().all(|byte| byte.is_ascii_digit() || byte.is_ascii_uppercase()) || !code[18..].iter().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse LEI Error: {}", String::from_utf8_lossy(bytes))));
+    }
+    let lei = Lei { code };
+    if !checksum::MOD_97_10.verify(&expand(lei.as_str())) {
+      return Err(UsciError::CommonError(format!("Verify LEI Check Error: {}", lei.check())));
+    }
+    return Ok(lei);
+  }
+
+  // Appends the check digits to an 18-char body.
+  pub fn complete(body: &str) -> UsciResult<Self> {
+    let body = body.trim().to_ascii_uppercase();
+    if body.len() != 18 || !body.bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+      return Err(UsciError::CommonError(format!("Parse LEI Error: {}", body)));
+    }
+    let check = checksum::MOD_97_10.compute(&expand(&body))?;
+    return Lei::from_str(&format!("{}{}", body, check));
+  }
+
+  pub fn from_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    let body: String = (0..18).map(|_| checksum::ALPHANUMERIC[rng.gen_range(0..checksum::ALPHANUMERIC.len())] as char).collect();
+    return Lei::complete(&body).unwrap();
+  }
+
+  pub fn lou(&self) -> &str {
+    return &self.as_str()[..4];
+  }
+
+  pub fn entity(&self) -> &str {
+    return &self.as_str()[4..18];
+  }
+
+  pub fn check(&self) -> &str {
+    return &self.as_str()[18..];
+  }
+
+  pub fn as_str(&self) -> &str {
+    return std::str::from_utf8(&self.code).unwrap();
+  }
+
+}
+
+impl FromStr for Lei {
+  type Err = UsciError;
+
+  fn from_str(code: &str) -> UsciResult<Self> {
+    return Lei::from_bytes(code.trim().to_ascii_uppercase().as_bytes());
+  }
+}
+
+impl Display for Lei {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.as_str())
+  }
+}
+
+impl AsRef<str> for Lei {
+
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+// USCI to LEI links. A code linked to more than one LEI, or a LEI linked to
+// more than one code, is a mismatch: one of the links is wrong.
+#[derive(Debug, Clone, Default)]
+pub struct CrossReference {
+  leis: HashMap<UsciCode, Vec<Lei>>,
+  codes: HashMap<Lei, Vec<UsciCode>>,
+  // Line number and reason of every row that was skipped while loading.
+  rejected: Vec<(usize, String)>
+}
+
+impl CrossReference {
+
+  pub fn new() -> Self {
+    return CrossReference::default();
+  }
+
+  pub fn add(&mut self, code: UsciCode, lei: Lei) {
+    let leis = self.leis.entry(code).or_default();
+    if !leis.contains(&lei) {
+      leis.push(lei);
+    }
+    let codes = self.codes.entry(lei).or_default();
+    if !codes.contains(&code) {
+      codes.push(code);
+    }
+  }
+
+  // One "USCI,LEI" pair per line, # starts a comment. A row with a bad code
+  // or LEI is skipped and kept in rejected, so one dirty row does not hide
+  // the rest of the file.
+  pub fn from_reader<R: BufRead>(reader: R) -> UsciResult<Self> {
+    let mut xref = CrossReference::new();
+    for (index, line) in reader.lines().enumerate() {
+      let line = line?;
+      let content = line.split('#').next().unwrap().trim();
+      if content.is_empty() {
+        continue;
+      }
+      let fields: Vec<&str> = content.split(',').map(|field| field.trim()).collect();
+      let parsed = match fields.as_slice() {
+        [code, lei] => UsciCode::from_str(code).and_then(|code| Lei::from_str(lei).map(|lei| (code, lei))),
+        _ => Err(UsciError::CommonError(format!("Parse LEI Cross Reference Error: {}", line)))
+      };
+      match parsed {
+        Ok((code, lei)) => xref.add(code, lei),
+        Err(err) => xref.rejected.push((index + 1, err.to_string()))
+      }
+    }
+    return Ok(xref);
+  }
+
+  pub fn rejected(&self) -> &[(usize, String)] {
+    return &self.rejected;
+  }
+
+  pub fn leis(&self, code: &UsciCode) -> &[Lei] {
+    return self.leis.get(code).map(|leis| leis.as_slice()).unwrap_or(&[]);
+  }
+
+  pub fn codes(&self, lei: &Lei) -> &[UsciCode] {
+    return self.codes.get(lei).map(|codes| codes.as_slice()).unwrap_or(&[]);
+  }
+
+  pub fn is_mismatched(&self, code: &UsciCode, lei: &Lei) -> bool {
+    return self.leis(code).len() > 1 || self.codes(lei).len() > 1;
+  }
+
+}
@@ -0,0 +1,37 @@
+#![allow(clippy::needless_return)]
+
+pub mod usci;
+pub mod address;
+pub mod checksum;
+pub mod correct;
+pub mod solve;
+pub mod space;
+pub mod taxid;
+pub mod common;
+pub mod division;
+pub mod homoglyph;
+pub mod idcard;
+pub mod identify;
+pub mod invalid;
+pub mod legal_form;
+pub mod lei;
+pub mod realistic;
+pub mod record;
+pub mod value_parser;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "fake")]
+mod fake;
+#[cfg(feature = "validator")]
+pub mod validator;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "diesel")]
+mod diesel;
+
+pub use crate::common::{UsciError, UsciResult};
+pub use crate::usci::{Code01, Code02, Code03, Code04, Code05, PackedUsciCode, UsciCode, UsciCodeBuilder};
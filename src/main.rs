@@ -1,13 +1,33 @@
-mod usci;
-mod common;
+#![allow(clippy::needless_return)]
 
-use std::io::{stdout, Write};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{stderr, stdin, stdout, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
 use std::process::exit;
 use std::str::FromStr;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use rand::rngs::ThreadRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
-use crate::common::UsciResult;
-use crate::usci::{Code01, Code02, Code03, Code04, Code05, UsciCode};
+use usci::{Code01, Code02, Code03, Code04, Code05, UsciCode, UsciError, UsciResult};
+use usci::address::{check_address, RegionMatch};
+use usci::correct::{ConfusionMatrix, Corrector};
+use usci::division;
+use usci::homoglyph::SpoofedChar;
+use usci::idcard::{mask, ResidentId};
+use usci::identify::identify;
+use usci::invalid::Defect;
+use usci::legal_form::{category_label, check_name};
+use usci::lei::{CrossReference, Lei};
+use usci::realistic::{RealisticGenerator, Weights};
+use usci::record::{self, Record};
+use usci::solve::Solver;
+use usci::space::{CodeSpace, Permutation};
+use usci::taxid::{mask_rejected, TaxpayerForm, TaxpayerId};
+use usci::usci::PACKED_BYTES;
 
 fn main() {
   let command: UsciCommand = UsciCommand::parse();
@@ -21,6 +41,30 @@ fn main() {
     UsciCommand::Info(command) => {
       info_command(command)
     }
+    UsciCommand::Complete(command) => {
+      complete_command(command)
+    }
+    UsciCommand::Solve(command) => {
+      solve_command(command)
+    }
+    UsciCommand::Correct(command) => {
+      correct_command(command)
+    }
+    UsciCommand::Identify(command) => {
+      identify_command(command)
+    }
+    UsciCommand::CheckNames(command) => {
+      check_names_command(command)
+    }
+    UsciCommand::CheckAddresses(command) => {
+      check_addresses_command(command)
+    }
+    UsciCommand::Pack(command) => {
+      pack_command(command)
+    }
+    UsciCommand::Unpack(command) => {
+      unpack_command(command)
+    }
   };
   match result {
     Ok(_) => {
@@ -35,20 +79,214 @@ fn main() {
 
 fn generate_command(command: GenerateCommand) -> UsciResult<()> {
   let mut rng = rand::thread_rng();
-  for _ in 0..command.count {
-    let code = UsciCode::from_random(&mut rng);
-    writeln!(stdout(), "{}", code.get_code())?;
+  let constrained = command.realistic || command.safe || command.region.is_some() || command.category.is_some() || command.range.is_some();
+  if constrained && !matches!(command.kind, Kind::Usci | Kind::TaxId) {
+    return Err(UsciError::CommonError("Generate Error: --realistic, --safe, --region, --category and --range only apply to usci codes".to_string()));
+  }
+  if command.records.is_some() && !matches!(command.kind, Kind::Usci) {
+    return Err(UsciError::CommonError("Generate Error: --records only applies to usci codes".to_string()));
+  }
+  if command.weights.is_some() && !command.realistic && command.records.is_none() {
+    return Err(UsciError::CommonError("Generate Error: --weights only applies with --realistic or --records".to_string()));
   }
+  if let Some(RecordFormat::Sql) = command.records {
+    if !record::is_sql_identifier(&command.table) {
+      return Err(UsciError::CommonError(format!("Parse Table Name Error: {}", command.table)));
+    }
+  }
+  let mut space = CodeSpace::new();
+  if let Some(region) = command.region.as_ref() {
+    space = space.region(region)?;
+  }
+  if let Some(category) = command.category.as_ref() {
+    space = space.category(category)?;
+  }
+  // Records need a known region for their address, so they always come from
+  // the realistic generator, narrowed to the space when one is given.
+  let realistic = if command.realistic || command.records.is_some() {
+    let weights = match &command.weights {
+      Some(file) => Weights::from_reader(BufReader::new(File::open(file)?))?,
+      None => Weights::default()
+    };
+    Some(RealisticGenerator::within(&weights, &space)?)
+  } else {
+    None
+  };
+  if let Some(range) = command.range.as_ref() {
+    return generate_range(&command, &space, range);
+  }
+  let mixed = command.error_rate.is_some();
+  if (mixed || !command.invalid.is_empty()) && !matches!(command.kind, Kind::Usci) {
+    return Err(UsciError::CommonError("Generate Error: --invalid and --error-rate only apply to usci codes".to_string()));
+  }
+  if command.error_rate.is_some_and(|rate| !(0.0..=1.0).contains(&rate)) {
+    return Err(UsciError::CommonError("Generate Error: --error-rate must be between 0 and 1".to_string()));
+  }
+  let defects = if command.invalid.is_empty() && mixed { Defect::ALL.to_vec() } else { command.invalid.clone() };
+  let mut seen = HashSet::new();
+  if command.exclude.is_some() {
+    for line in open_input(&command.exclude)?.lines() {
+      let code = line?.trim().to_ascii_uppercase();
+      if !code.is_empty() {
+        seen.insert(code);
+      }
+    }
+  }
+  // Realistic and safe codes draw from spaces far larger than any run.
+  if matches!(command.kind, Kind::Usci | Kind::TaxId) && realistic.is_none() && !command.safe {
+    let excluded = seen.iter().filter(|code| UsciCode::from_str(code).is_ok_and(|code| space.contains(&code))).count() as u128;
+    let needed = if command.unique { command.count as u128 } else { 1 };
+    if space.size() < excluded + needed {
+      return Err(UsciError::CommonError(format!("Generate Error: {} codes requested, {} available after {} excluded", command.count, space.size() - excluded, excluded)));
+    }
+  }
+  let mut stdout = BufWriter::new(stdout().lock());
+  if mixed {
+    writeln!(stdout, "code,label")?;
+  }
+  match command.records {
+    Some(RecordFormat::Json) => writeln!(stdout, "[")?,
+    Some(RecordFormat::Csv) => writeln!(stdout, "{}", record::FIELDS.join(","))?,
+    _ => {}
+  }
+  let mut generated = 0;
+  while generated < command.count {
+    // The code itself is what must be unique, whatever line it ends up in.
+    let (code, line) = match command.kind {
+      Kind::Usci => {
+        let code = random_usci(&space, &realistic, command.safe, &mut rng);
+        let defect = match command.error_rate {
+          Some(rate) if !rng.gen_bool(rate) => None,
+          _ => defects.choose(&mut rng).copied()
+        };
+        let corrupted = match defect {
+          Some(defect) => match defect.corrupt(&code, &mut rng) {
+            Some(corrupted) => corrupted,
+            None => continue
+          },
+          None => code.to_string()
+        };
+        let line = match command.records {
+          Some(RecordFormat::Json) => {
+            let separator = if generated + 1 < command.count { "," } else { "" };
+            format!("  {}{}", Record::from_code(code, &mut rng).to_json(), separator)
+          }
+          Some(RecordFormat::Csv) => Record::from_code(code, &mut rng).to_csv(),
+          Some(RecordFormat::Sql) => Record::from_code(code, &mut rng).to_sql(&command.table)?,
+          None if mixed => format!("{},{}", corrupted, defect.map_or("valid", |defect| defect.get_code())),
+          None => corrupted.clone()
+        };
+        (corrupted, line)
+      }
+      Kind::IdCard => (ResidentId::from_random(&mut rng).to_string(), String::new()),
+      Kind::TaxId => (TaxpayerId::from(random_usci(&space, &realistic, command.safe, &mut rng)).to_string(), String::new()),
+      Kind::Lei => (Lei::from_random(&mut rng).to_string(), String::new())
+    };
+    if seen.contains(&code) {
+      continue;
+    }
+    writeln!(stdout, "{}", if line.is_empty() { &code } else { &line })?;
+    if command.unique {
+      seen.insert(code);
+    }
+    generated += 1;
+  }
+  if let Some(RecordFormat::Json) = command.records {
+    writeln!(stdout, "]")?;
+  }
+  stdout.flush()?;
   Ok(())
 }
 
+// Deterministic slice start..end of the space, so that workers given
+// disjoint ranges produce disjoint codes.
+fn generate_range(command: &GenerateCommand, space: &CodeSpace, range: &str) -> UsciResult<()> {
+  let parsed = range.split_once("..").and_then(|(start, end)| start.parse::<u128>().ok().zip(end.parse::<u128>().ok()));
+  let (start, end) = match parsed {
+    Some((start, end)) if start <= end && end <= space.size() => (start, end),
+    _ => return Err(UsciError::CommonError(format!("Parse Range Error: {} (the space holds {} codes)", range, space.size())))
+  };
+  let permutation = command.key.map(|key| Permutation::new(key, space.size()));
+  let mut stdout = BufWriter::new(stdout().lock());
+  for index in start..end {
+    let index = permutation.map_or(index, |permutation| permutation.permute(index));
+    let code = space.unrank(index)?;
+    match command.kind {
+      Kind::TaxId => writeln!(stdout, "{}", TaxpayerId::from(code))?,
+      _ => writeln!(stdout, "{}", code)?
+    }
+  }
+  stdout.flush()?;
+  Ok(())
+}
+
+fn random_usci(space: &CodeSpace, realistic: &Option<RealisticGenerator>, safe: bool, rng: &mut ThreadRng) -> UsciCode {
+  let code = match realistic {
+    Some(generator) => generator.generate(rng),
+    None => space.sample(rng)
+  };
+  return if safe { code.into_test_code(rng) } else { code };
+}
+
 fn verify_command(command: VerifyCommand) -> UsciResult<()> {
+  match command.kind {
+    Kind::Usci => verify_usci(command),
+    Kind::IdCard => verify_id_card(command),
+    Kind::TaxId => verify_tax_id(command),
+    Kind::Lei => verify_lei(command)
+  }
+}
+
+fn verify_id_card(command: VerifyCommand) -> UsciResult<()> {
   let mut stdout = StandardStream::stdout(ColorChoice::Auto);
   let mut color_spec = ColorSpec::new();
   for code_str in command.codes.iter() {
-    let code_result = UsciCode::from_str(code_str);
-    match code_result {
-      Ok(_code) => {
+    let shown = if command.unmask { code_str.to_string() } else { mask(code_str) };
+    match ResidentId::from_str(code_str) {
+      Ok(_id) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+        writeln!(stdout, "TRUE  {}", shown)?;
+      }
+      Err(_err) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", shown)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn verify_tax_id(command: VerifyCommand) -> UsciResult<()> {
+  let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+  let mut color_spec = ColorSpec::new();
+  for code_str in command.codes.iter() {
+    match TaxpayerId::from_str(code_str) {
+      Ok(id) => {
+        let shown = if command.unmask { id.to_string() } else { id.masked() };
+        stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+        writeln!(stdout, "TRUE  {} {}", shown, id.form().get_type())?;
+      }
+      Err(UsciError::SpoofedCharacter(spoofed)) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", code_str)?;
+        write_spoofed(&mut stdout, &spoofed)?;
+      }
+      Err(_err) => {
+        let shown = if command.unmask { code_str.to_string() } else { mask_rejected(code_str) };
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", shown)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn verify_lei(command: VerifyCommand) -> UsciResult<()> {
+  let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+  let mut color_spec = ColorSpec::new();
+  for code_str in command.codes.iter() {
+    match Lei::from_str(code_str) {
+      Ok(_lei) => {
         stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
         writeln!(stdout, "TRUE  {}", code_str)?;
       }
@@ -61,7 +299,182 @@ fn verify_command(command: VerifyCommand) -> UsciResult<()> {
   Ok(())
 }
 
+fn verify_usci(command: VerifyCommand) -> UsciResult<()> {
+  let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+  let mut color_spec = ColorSpec::new();
+  for code_str in command.codes.iter() {
+    let code_result = if command.normalize {
+      UsciCode::parse_normalized(code_str)
+    } else {
+      UsciCode::from_str(code_str).map(|code| (code, Vec::new()))
+    };
+    match code_result {
+      Ok((code, _spoofed)) if command.reject_test_codes && code.is_test_code() => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {} test code", code_str)?;
+      }
+      Ok((code, spoofed)) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+        if spoofed.is_empty() {
+          writeln!(stdout, "TRUE  {}", code_str)?;
+        } else {
+          writeln!(stdout, "TRUE  {} -> {}", code_str, code)?;
+          write_spoofed(&mut stdout, &spoofed)?;
+        }
+      }
+      Err(UsciError::SpoofedCharacter(spoofed)) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", code_str)?;
+        write_spoofed(&mut stdout, &spoofed)?;
+      }
+      Err(_err) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", code_str)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn write_spoofed(stdout: &mut StandardStream, spoofed: &[SpoofedChar]) -> UsciResult<()> {
+  stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+  for item in spoofed.iter() {
+    writeln!(stdout, "      SPOOFED {}", item)?;
+  }
+  Ok(())
+}
+
 fn info_command(command: InfoCommand) -> UsciResult<()> {
+  let xref = match &command.lei_xref {
+    Some(file) => {
+      let xref = CrossReference::from_reader(BufReader::new(File::open(file)?))?;
+      for (line, reason) in xref.rejected() {
+        let _ = writeln!(stderr(), "SKIPPED {}:{} ({})", file.display(), line, reason);
+      }
+      Some(xref)
+    }
+    None => None
+  };
+  match command.kind {
+    Kind::Usci => info_usci(command, xref),
+    Kind::IdCard => info_id_card(command),
+    Kind::TaxId => info_tax_id(command),
+    Kind::Lei => info_lei(command, xref)
+  }
+}
+
+fn info_lei(command: InfoCommand, xref: Option<CrossReference>) -> UsciResult<()> {
+  let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_bold(true);
+  for code_str in command.codes.iter() {
+    stdout.set_color(color_spec.set_fg(Some(Color::White)))?;
+    writeln!(stdout, "{}: {}", Lei::code_type(), code_str)?;
+    match Lei::from_str(code_str) {
+      Ok(lei) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+        writeln!(stdout, "{:>14}: LOU前缀", lei.lou())?;
+        writeln!(stdout, "{:>14}: 实体代码", lei.entity())?;
+        writeln!(stdout, "{:>14}: 校验码", lei.check())?;
+        if let Some(xref) = &xref {
+          for code in xref.codes(&lei) {
+            write_xref(&mut stdout, code, xref.is_mismatched(code, &lei), "统一社会信用代码")?;
+          }
+        }
+      }
+      Err(_err) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", code_str)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn write_xref<T: Display>(stdout: &mut StandardStream, code: &T, mismatched: bool, code_type: &str) -> UsciResult<()> {
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_bold(true);
+  if mismatched {
+    stdout.set_color(color_spec.set_fg(Some(Color::Yellow)))?;
+    writeln!(stdout, "{:>10}: {} MISMATCH", code, code_type)?;
+  } else {
+    stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+    writeln!(stdout, "{:>10}: {}", code, code_type)?;
+  }
+  Ok(())
+}
+
+fn info_id_card(command: InfoCommand) -> UsciResult<()> {
+  let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_bold(true);
+  for code_str in command.codes.iter() {
+    let shown = if command.unmask { code_str.to_string() } else { mask(code_str) };
+    stdout.set_color(color_spec.set_fg(Some(Color::White)))?;
+    writeln!(stdout, "{}: {}", ResidentId::code_type(), shown)?;
+    match ResidentId::from_str(code_str) {
+      Ok(id) => {
+        let (year, month, day) = id.birth_date();
+        let birth_date = if command.unmask { format!("{:04}-{:02}-{:02}", year, month, day) } else { "****-**-**".to_string() };
+        stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+        writeln!(stdout, "{:>10}: 行政区划 {}", id.region(), region_name(id.region()))?;
+        writeln!(stdout, "{:>10}: 出生日期", birth_date)?;
+        writeln!(stdout, "{:>10}: 顺序码 {}", id.sequence(), id.gender().get_type())?;
+        writeln!(stdout, "{:>10}: 校验码", id.check())?;
+      }
+      Err(_err) => {
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", shown)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn info_tax_id(command: InfoCommand) -> UsciResult<()> {
+  let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+  let mut color_spec = ColorSpec::new();
+  color_spec.set_bold(true);
+  for code_str in command.codes.iter() {
+    match TaxpayerId::from_str(code_str) {
+      Ok(id) => {
+        let code = id.as_str();
+        let shown = if command.unmask { id.to_string() } else { id.masked() };
+        stdout.set_color(color_spec.set_fg(Some(Color::White)))?;
+        writeln!(stdout, "{}: {} {}", TaxpayerId::code_type(), shown, id.form().get_type())?;
+        stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
+        match id.form() {
+          TaxpayerForm::Usci | TaxpayerForm::ResidentId => {}
+          TaxpayerForm::Legacy => {
+            writeln!(stdout, "{:>10}: 行政区划 {}", &code[..6], region_name(&code[..6]))?;
+            writeln!(stdout, "{:>10}: {}", &code[6..], Code04::code_type())?;
+          }
+          TaxpayerForm::LegacyResidentIdWithSuffix | TaxpayerForm::ResidentIdWithSuffix => {
+            let split = code.len() - 2;
+            let prefix = if command.unmask { code[..split].to_string() } else { mask(&code[..split]) };
+            writeln!(stdout, "{:>10}: {}", prefix, ResidentId::code_type())?;
+            writeln!(stdout, "{:>10}: 顺序码", &code[split..])?;
+          }
+        }
+      }
+      Err(_err) => {
+        let shown = if command.unmask { code_str.to_string() } else { mask_rejected(code_str) };
+        stdout.set_color(color_spec.set_fg(Some(Color::White)))?;
+        writeln!(stdout, "{}: {}", TaxpayerId::code_type(), shown)?;
+        stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
+        writeln!(stdout, "FALSE {}", shown)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn region_name(region: &str) -> String {
+  let names: Vec<&str> = [division::province(region), division::prefecture(region)].iter().flatten().copied().collect();
+  return names.join(" ");
+}
+
+fn info_usci(command: InfoCommand, xref: Option<CrossReference>) -> UsciResult<()> {
   let mut stdout = StandardStream::stdout(ColorChoice::Auto);
   let mut color_spec = ColorSpec::new();
   color_spec.set_bold(true);
@@ -72,11 +485,16 @@ fn info_command(command: InfoCommand) -> UsciResult<()> {
     match code_result {
       Ok(code) => {
         stdout.set_color(color_spec.set_fg(Some(Color::Green)))?;
-        writeln!(stdout, "{:>10}: {} {}", code.code01.get_code(), Code01::code_type(), code.code01.get_type())?;
-        writeln!(stdout, "{:>10}: {} {}", code.code02.get_code(), Code02::code_type(),code.code02.get_type())?;
-        writeln!(stdout, "{:>10}: {}", code.code03.to_string(), Code03::code_type())?;
-        writeln!(stdout, "{:>10}: {}", code.code04.to_string(), Code04::code_type())?;
-        writeln!(stdout, "{:>10}: {}", code.code05.to_string(), Code05::code_type())?;
+        writeln!(stdout, "{:>10}: {} {}", code.code01(), Code01::code_type(), code.code01().get_type())?;
+        writeln!(stdout, "{:>10}: {} {}", code.code02().get_code(), Code02::code_type(), code.code02().get_type())?;
+        writeln!(stdout, "{:>10}: {} {}", code.code03(), Code03::code_type(), region_name(code.code03()))?;
+        writeln!(stdout, "{:>10}: {}", code.code04(), Code04::code_type())?;
+        writeln!(stdout, "{:>10}: {}", code.code05(), Code05::code_type())?;
+        if let Some(xref) = &xref {
+          for lei in xref.leis(&code) {
+            write_xref(&mut stdout, lei, xref.is_mismatched(&code, lei), Lei::code_type())?;
+          }
+        }
       }
       Err(_err) => {
         stdout.set_color(color_spec.set_fg(Some(Color::Red)))?;
@@ -87,6 +505,238 @@ fn info_command(command: InfoCommand) -> UsciResult<()> {
   Ok(())
 }
 
+fn open_input(file: &Option<PathBuf>) -> UsciResult<Box<dyn BufRead>> {
+  match file {
+    Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    None => Ok(Box::new(BufReader::new(stdin())))
+  }
+}
+
+fn open_output(file: &Option<PathBuf>) -> UsciResult<Box<dyn Write>> {
+  match file {
+    Some(path) => Ok(Box::new(BufWriter::new(File::create(path)?))),
+    None => Ok(Box::new(BufWriter::new(stdout())))
+  }
+}
+
+fn read_codes(codes: Vec<String>) -> UsciResult<Vec<String>> {
+  if !codes.is_empty() {
+    return Ok(codes);
+  }
+  let mut lines = Vec::new();
+  for line in stdin().lock().lines() {
+    let line = line?;
+    let code_str = line.trim();
+    if !code_str.is_empty() {
+      lines.push(code_str.to_string());
+    }
+  }
+  return Ok(lines);
+}
+
+fn complete_command(command: CompleteCommand) -> UsciResult<()> {
+  let mut stdout = stdout().lock();
+  let mut failed = 0;
+  for body in read_codes(command.bodies)?.iter() {
+    match UsciCode::complete(body) {
+      Ok(code) => {
+        writeln!(stdout, "{}", code)?;
+      }
+      Err(err) => {
+        failed += 1;
+        let _ = writeln!(stderr(), "FALSE {} ({})", body, err);
+      }
+    }
+  }
+  if failed > 0 {
+    return Err(UsciError::CommonError(format!("{} bodies could not be completed", failed)));
+  }
+  Ok(())
+}
+
+fn solve_command(command: SolveCommand) -> UsciResult<()> {
+  let mut solver = Solver::new(&command.pattern)?;
+  if let Some(region) = command.region.as_ref() {
+    solver = solver.region(region)?;
+  }
+  if let Some(category) = command.category.as_ref() {
+    solver = solver.category(category)?;
+  }
+  // When the count needs enumerating, keep the codes within the limit from
+  // that same pass rather than enumerating again to print them.
+  let (count, codes) = match solver.known_count() {
+    Some(count) => (count, None),
+    None => {
+      let mut count: u128 = 0;
+      let mut codes = Vec::new();
+      for code in solver.iter() {
+        count += 1;
+        if !command.count_only && count <= command.limit as u128 {
+          codes.push(code);
+        }
+      }
+      (count, Some(codes))
+    }
+  };
+  let mut stdout = stdout().lock();
+  if command.count_only {
+    writeln!(stdout, "{}", count)?;
+  } else if count > command.limit as u128 {
+    writeln!(stdout, "{} candidates, more than the limit of {}; narrow down with --region/--category or raise --limit", count, command.limit)?;
+  } else if let Some(codes) = codes {
+    for code in codes {
+      writeln!(stdout, "{}", code)?;
+    }
+  } else {
+    for code in solver.iter() {
+      writeln!(stdout, "{}", code)?;
+    }
+  }
+  Ok(())
+}
+
+fn correct_command(command: CorrectCommand) -> UsciResult<()> {
+  let matrix = match command.confusion.as_ref() {
+    Some(path) => ConfusionMatrix::from_reader(BufReader::new(File::open(path)?))?,
+    None => ConfusionMatrix::default()
+  };
+  let corrector = Corrector::new(matrix).max_edits(command.max_edits);
+  let confidences = match command.confidence.as_ref() {
+    Some(confidence) => {
+      let mut values = Vec::new();
+      for value in confidence.split(',') {
+        match value.trim().parse::<f64>() {
+          Ok(value) => values.push(value),
+          Err(_) => return Err(UsciError::CommonError(format!("Parse Confidence Error: {}", value)))
+        }
+      }
+      Some(values)
+    }
+    None => None
+  };
+  let corrections = corrector.correct(&command.code, confidences.as_deref())?;
+  let mut stdout = stdout().lock();
+  for correction in corrections.iter().take(command.top) {
+    let edits: Vec<String> = correction.edits.iter()
+      .map(|(index, from, to)| format!("{}:{}->{}", index + 1, from, to))
+      .collect();
+    writeln!(stdout, "{:.6} {} {}", correction.score, correction.code, edits.join(" "))?;
+  }
+  if corrections.is_empty() {
+    writeln!(stdout, "FALSE {}", command.code)?;
+  }
+  Ok(())
+}
+
+fn identify_command(command: IdentifyCommand) -> UsciResult<()> {
+  let input = open_input(&command.file)?;
+  let mut output = open_output(&command.output)?;
+  for line in input.lines() {
+    let line = line?;
+    let tags: Vec<String> = identify(&line).iter()
+      .map(|identification| format!("{}={}", identification.kind, if identification.valid { "TRUE" } else { "FALSE" }))
+      .collect();
+    let tags = if tags.is_empty() { "unknown".to_string() } else { tags.join(",") };
+    writeln!(output, "{}\t{}", line, tags)?;
+  }
+  output.flush()?;
+  Ok(())
+}
+
+// Batch input of "text,code" CSV rows. The code is the last field, so an
+// unquoted name may still hold commas. A first row whose last field has no
+// digit is a header and gets a result column instead of a check.
+fn check_pairs(file: &Option<PathBuf>, output_file: &Option<PathBuf>, check: impl Fn(&str, &UsciCode) -> String) -> UsciResult<()> {
+  let input = open_input(file)?;
+  let mut output = open_output(output_file)?;
+  for (index, line) in input.lines().enumerate() {
+    let line = line?;
+    let fields = record::parse_csv_line(&line);
+    let (code, text) = fields.split_last().unwrap();
+    if index == 0 && !code.chars().any(|ch| ch.is_ascii_digit()) {
+      writeln!(output, "{}\tresult", line)?;
+      continue;
+    }
+    let result = match UsciCode::from_str(code.trim()) {
+      Ok(code) => check(text.join(",").trim(), &code),
+      Err(_) => "INVALID".to_string()
+    };
+    writeln!(output, "{}\t{}", line, result)?;
+  }
+  output.flush()?;
+  Ok(())
+}
+
+fn check_names_command(command: CheckNamesCommand) -> UsciResult<()> {
+  return check_pairs(&command.file, &command.output, |name, code| {
+    let check = check_name(name, code);
+    if check.legal_form.is_none() {
+      "UNKNOWN".to_string()
+    } else if check.is_consistent() {
+      "TRUE".to_string()
+    } else {
+      let expected: Vec<String> = check.expected().iter().map(category_label).collect();
+      format!("FALSE expected {} found {}", expected.join(" / "), category_label(&check.actual))
+    }
+  });
+}
+
+fn check_addresses_command(command: CheckAddressesCommand) -> UsciResult<()> {
+  return check_pairs(&command.file, &command.output, |address, code| {
+    let check = check_address(address, code);
+    let parsed = check.address.prefecture.or(check.address.province).map(|(region, _)| region_name(region)).unwrap_or_default();
+    let mismatch = |level: &str| format!("FALSE {} {}: address {} / code {}", level, check.confidence, parsed, region_name(code.code03()));
+    match check.result {
+      RegionMatch::Same => format!("TRUE {}", check.confidence),
+      RegionMatch::DifferentProvince => mismatch("province"),
+      RegionMatch::DifferentPrefecture => mismatch("prefecture"),
+      RegionMatch::Unknown => "UNKNOWN".to_string()
+    }
+  });
+}
+
+fn pack_command(command: PackCommand) -> UsciResult<()> {
+  let input = open_input(&command.file)?;
+  let mut output = open_output(&command.output)?;
+  for line in input.lines() {
+    let line = line?;
+    let code_str = line.trim();
+    if code_str.is_empty() {
+      continue;
+    }
+    let code = UsciCode::from_str(code_str)?;
+    output.write_all(&code.to_packed_bytes())?;
+  }
+  output.flush()?;
+  Ok(())
+}
+
+fn unpack_command(command: UnpackCommand) -> UsciResult<()> {
+  let mut input = open_input(&command.file)?;
+  let mut output = open_output(&command.output)?;
+  let mut record = [0u8; PACKED_BYTES];
+  loop {
+    let mut filled = 0;
+    while filled < PACKED_BYTES {
+      let size = input.read(&mut record[filled..])?;
+      if size == 0 {
+        break;
+      }
+      filled += size;
+    }
+    if filled == 0 {
+      break;
+    }
+    if filled < PACKED_BYTES {
+      return Err(UsciError::CommonError(format!("Unpack Code Error: truncated record of {} bytes", filled)));
+    }
+    let code = UsciCode::from_packed_bytes(&record)?;
+    writeln!(output, "{}", code)?;
+  }
+  output.flush()?;
+  Ok(())
+}
+
 #[derive(Debug, Parser)]
 #[clap(about = "little tool of usci code")]
 enum UsciCommand {
@@ -98,14 +748,95 @@ enum UsciCommand {
   Verify(VerifyCommand),
 
   #[clap(about = "Show the information of codes")]
-  Info(InfoCommand)
+  Info(InfoCommand),
+
+  #[clap(about = "Append the check char to 17-char code bodies")]
+  Complete(CompleteCommand),
+
+  #[clap(about = "List every valid code matching a pattern with ? wildcards")]
+  Solve(SolveCommand),
+
+  #[clap(about = "Rank the most probable valid codes for an OCR reading")]
+  Correct(CorrectCommand),
+
+  #[clap(about = "Tag every line with the kinds of identifier it could be")]
+  Identify(IdentifyCommand),
+
+  #[clap(about = "Check name,code pairs for a legal form that contradicts the category")]
+  CheckNames(CheckNamesCommand),
+
+  #[clap(about = "Check address,code pairs for an address outside the registration region")]
+  CheckAddresses(CheckAddressesCommand),
+
+  #[clap(about = "Pack codes into fixed-width binary records")]
+  Pack(PackCommand),
+
+  #[clap(about = "Unpack binary records back into codes")]
+  Unpack(UnpackCommand)
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Kind {
+  Usci,
+  IdCard,
+  TaxId,
+  Lei
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum RecordFormat {
+  Json,
+  Csv,
+  Sql
 }
 
 #[derive(Debug, Parser)]
 struct GenerateCommand {
 
   #[clap(long = "count", short = 'c', default_value = "1")]
-  count: u32
+  count: u32,
+
+  #[clap(long = "kind", short = 'k', value_enum, default_value = "usci")]
+  kind: Kind,
+
+  #[clap(long = "realistic", help = "Follow the department, category, region and Code04 shares of real registers")]
+  realistic: bool,
+
+  #[clap(long = "weights", help = "File of department/category/region/ma weights for --realistic and --records")]
+  weights: Option<PathBuf>,
+
+  #[clap(long = "safe", conflicts_with = "region", help = "Only emit fictitious codes, registered in the non-existent region 00")]
+  safe: bool,
+
+  #[clap(long = "region", short = 'r', conflicts_with = "realistic", help = "Code03 prefix, e.g. 44 or 440300")]
+  region: Option<String>,
+
+  #[clap(long = "category", short = 't', conflicts_with = "realistic", help = "Code01 or Code01 with Code02, e.g. 9 or 91")]
+  category: Option<String>,
+
+  #[clap(long = "unique", short = 'u', help = "Never emit the same code twice")]
+  unique: bool,
+
+  #[clap(long = "exclude", help = "File of codes already in use, one per line, never to be emitted")]
+  exclude: Option<PathBuf>,
+
+  #[clap(long = "invalid", value_parser = Defect::from_str, conflicts_with = "range", help = "Break codes in this way: length, code01, code02, code03, code04, check or transposed; repeat to mix")]
+  invalid: Vec<Defect>,
+
+  #[clap(long = "error-rate", conflicts_with = "range", help = "Emit a code,label CSV where this share of codes is broken, by every way unless --invalid is given")]
+  error_rate: Option<f64>,
+
+  #[clap(long = "records", value_enum, conflicts_with_all = &["invalid", "error-rate", "range", "safe"], help = "Emit whole organization records with name, address, organization code and tax ID")]
+  records: Option<RecordFormat>,
+
+  #[clap(long = "table", default_value = "organizations", help = "Table of the INSERT statements of --records sql")]
+  table: String,
+
+  #[clap(long = "range", conflicts_with_all = &["realistic", "safe", "exclude"], help = "Emit codes start..end of the space in order, instead of --count random ones")]
+  range: Option<String>,
+
+  #[clap(long = "key", requires = "range", help = "Shuffle the space with this key, so neighbouring indices give unrelated codes")]
+  key: Option<u64>
 
 }
 
@@ -115,12 +846,136 @@ struct VerifyCommand {
   // #[clap(long = "file", short = 'f')]
   // file: Option<PathBuf>,
 
+  #[clap(long = "normalize", help = "Replace look-alike chars and verify the result instead of rejecting them")]
+  normalize: bool,
+
+  #[clap(long = "reject-test-codes", help = "Report codes made by generate --safe as invalid")]
+  reject_test_codes: bool,
+
+  #[clap(long = "kind", short = 'k', value_enum, default_value = "usci")]
+  kind: Kind,
+
+  #[clap(long = "unmask", help = "Show the birth date of ID card based numbers")]
+  unmask: bool,
+
   codes: Vec<String>
 }
 
 #[derive(Debug, Parser)]
 struct InfoCommand {
 
+  #[clap(long = "kind", short = 'k', value_enum, default_value = "usci")]
+  kind: Kind,
+
+  #[clap(long = "unmask", help = "Show the birth date of ID card based numbers")]
+  unmask: bool,
+
+  #[clap(long = "lei-xref", help = "File of USCI,LEI pairs to look codes up in")]
+  lei_xref: Option<PathBuf>,
+
   codes: Vec<String>
 
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Parser)]
+struct CompleteCommand {
+
+  #[clap(help = "17-char code bodies, read from stdin if omitted")]
+  bodies: Vec<String>
+
+}
+
+#[derive(Debug, Parser)]
+struct SolveCommand {
+
+  #[clap(help = "18-char code, or 17-char body, with ? for unknown chars")]
+  pattern: String,
+
+  #[clap(long = "region", short = 'r', help = "Code03 prefix, e.g. 44 or 440300")]
+  region: Option<String>,
+
+  #[clap(long = "category", short = 't', help = "Code01 or Code01 with Code02, e.g. 9 or 91")]
+  category: Option<String>,
+
+  #[clap(long = "limit", short = 'l', default_value = "100", help = "Only report the count above this many candidates")]
+  limit: u64,
+
+  #[clap(long = "count", help = "Only report the number of candidates")]
+  count_only: bool
+
+}
+
+#[derive(Debug, Parser)]
+struct CorrectCommand {
+
+  #[clap(help = "18 chars as read by OCR")]
+  code: String,
+
+  #[clap(long = "confidence", help = "Comma separated confidence of each of the 18 chars")]
+  confidence: Option<String>,
+
+  #[clap(long = "confusion", help = "File of \"observed actual weight\" lines replacing the default confusions")]
+  confusion: Option<PathBuf>,
+
+  #[clap(long = "max-edits", short = 'e', default_value = "2", help = "Most chars to replace, a swap of two neighbours counting as one")]
+  max_edits: usize,
+
+  #[clap(long = "top", short = 'n', default_value = "5")]
+  top: usize
+
+}
+
+#[derive(Debug, Parser)]
+struct CheckNamesCommand {
+
+  #[clap(help = "File with one name,code pair per line, stdin if omitted")]
+  file: Option<PathBuf>,
+
+  #[clap(long = "output", short = 'o', help = "Output file, stdout if omitted")]
+  output: Option<PathBuf>
+
+}
+
+#[derive(Debug, Parser)]
+struct CheckAddressesCommand {
+
+  #[clap(help = "File with one address,code pair per line, stdin if omitted")]
+  file: Option<PathBuf>,
+
+  #[clap(long = "output", short = 'o', help = "Output file, stdout if omitted")]
+  output: Option<PathBuf>
+
+}
+
+#[derive(Debug, Parser)]
+struct IdentifyCommand {
+
+  #[clap(help = "File with one identifier per line, stdin if omitted")]
+  file: Option<PathBuf>,
+
+  #[clap(long = "output", short = 'o', help = "Output file, stdout if omitted")]
+  output: Option<PathBuf>
+
+}
+
+#[derive(Debug, Parser)]
+struct PackCommand {
+
+  #[clap(help = "File with one code per line, stdin if omitted")]
+  file: Option<PathBuf>,
+
+  #[clap(long = "output", short = 'o', help = "Binary output file, stdout if omitted")]
+  output: Option<PathBuf>
+
+}
+
+#[derive(Debug, Parser)]
+struct UnpackCommand {
+
+  #[clap(help = "Binary file written by pack, stdin if omitted")]
+  file: Option<PathBuf>,
+
+  #[clap(long = "output", short = 'o', help = "Output file, stdout if omitted")]
+  output: Option<PathBuf>
+
+}
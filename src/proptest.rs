@@ -0,0 +1,92 @@
+use std::str::FromStr;
+use proptest::prelude::*;
+use proptest::sample::select;
+use crate::invalid::{Defect, NearValid};
+use crate::space::CodeSpace;
+use crate::usci::{Code01, Code02, Code03, Code04, Code05, UsciCode, CODE_CHARS};
+
+// Codes are drawn by rank, so a failing case shrinks toward the first code
+// of the space: lowest category, region and Code04.
+pub fn usci_code(space: CodeSpace) -> BoxedStrategy<UsciCode> {
+  return (0..space.size()).prop_map(move |index| space.unrank(index).unwrap()).boxed();
+}
+
+// Codes of the space broken by the one defect. The corruption is seeded, so
+// it shrinks along with the original code.
+pub fn near_valid(space: CodeSpace, defect: Defect) -> BoxedStrategy<NearValid> {
+  return (usci_code(space), any::<u64>())
+    .prop_filter_map("no place for the defect", move |(original, seed)| NearValid::new(original, defect, seed))
+    .boxed();
+}
+
+impl Arbitrary for UsciCode {
+  type Parameters = CodeSpace;
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(space: CodeSpace) -> Self::Strategy {
+    return usci_code(space);
+  }
+}
+
+impl Arbitrary for NearValid {
+  type Parameters = CodeSpace;
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(space: CodeSpace) -> Self::Strategy {
+    return select(Defect::ALL.to_vec()).prop_flat_map(move |defect| near_valid(space.clone(), defect)).boxed();
+  }
+}
+
+impl Arbitrary for Defect {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    return select(Defect::ALL.to_vec()).boxed();
+  }
+}
+
+impl Arbitrary for Code01 {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    return select(Code01::ALL.to_vec()).boxed();
+  }
+}
+
+impl Arbitrary for Code02 {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    return select(Code02::ALL.to_vec()).boxed();
+  }
+}
+
+impl Arbitrary for Code03 {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    return (0..1_000_000u32).prop_map(|index| Code03::new(&format!("{:06}", index)).unwrap()).boxed();
+  }
+}
+
+impl Arbitrary for Code04 {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    return (0..CodeSpace::org_codes()).prop_map(Code04::from_index).boxed();
+  }
+}
+
+impl Arbitrary for Code05 {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    return select(CODE_CHARS.to_vec()).prop_map(|ch| Code05::from_str(ch.encode_utf8(&mut [0; 4])).unwrap()).boxed();
+  }
+}
@@ -0,0 +1,114 @@
+use std::str::FromStr;
+use quickcheck::{Arbitrary, Gen};
+use crate::invalid::{Defect, NearValid};
+use crate::space::CodeSpace;
+use crate::usci::{Code01, Code02, Code03, Code04, Code05, UsciCode, CODE_CHARS};
+
+// Everything before the value in its list, simplest first.
+fn earlier<T: Copy + PartialEq + 'static>(all: &'static [T], value: T) -> Box<dyn Iterator<Item = T>> {
+  return Box::new(all.iter().copied().take_while(move |item| *item != value));
+}
+
+// Codes are drawn by rank in the full space and shrink toward lower ranks,
+// ending at the first code: lowest category, region and Code04.
+impl Arbitrary for UsciCode {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    let space = CodeSpace::new();
+    return space.unrank(u128::arbitrary(g) % space.size()).unwrap();
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let space = CodeSpace::new();
+    return Box::new(space.rank(self).unwrap().shrink().map(move |index| space.unrank(index).unwrap()));
+  }
+}
+
+// Shrinks the original code and breaks each candidate again with the same
+// seed, so the defect stays where it was wherever the code allows.
+impl Arbitrary for NearValid {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    let defect = Defect::arbitrary(g);
+    loop {
+      let original = UsciCode::arbitrary(g);
+      if let Some(near_valid) = NearValid::new(original, defect, u64::arbitrary(g)) {
+        return near_valid;
+      }
+    }
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let (defect, seed) = (self.defect, self.seed);
+    return Box::new(self.original.shrink().filter_map(move |original| NearValid::new(original, defect, seed)));
+  }
+}
+
+impl Arbitrary for Defect {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    return *g.choose(&Defect::ALL).unwrap();
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    return earlier(&Defect::ALL, *self);
+  }
+}
+
+impl Arbitrary for Code01 {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    return *g.choose(&Code01::ALL).unwrap();
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    return earlier(&Code01::ALL, *self);
+  }
+}
+
+impl Arbitrary for Code02 {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    return *g.choose(&Code02::ALL).unwrap();
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    return earlier(&Code02::ALL, *self);
+  }
+}
+
+impl Arbitrary for Code03 {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    return Code03::new(&format!("{:06}", u32::arbitrary(g) % 1_000_000)).unwrap();
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let index: u32 = self.as_str().parse().unwrap();
+    return Box::new(index.shrink().map(|index| Code03::new(&format!("{:06}", index)).unwrap()));
+  }
+}
+
+impl Arbitrary for Code04 {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    return Code04::from_index(u128::arbitrary(g) % CodeSpace::org_codes());
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    return Box::new(self.index().shrink().map(Code04::from_index));
+  }
+}
+
+impl Arbitrary for Code05 {
+
+  fn arbitrary(g: &mut Gen) -> Self {
+    let ch = *g.choose(&CODE_CHARS).unwrap();
+    return Code05::from_str(ch.encode_utf8(&mut [0; 4])).unwrap();
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let chars = earlier(&CODE_CHARS, self.get_code());
+    return Box::new(chars.map(|ch| Code05::from_str(ch.encode_utf8(&mut [0; 4])).unwrap()));
+  }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str::FromStr;
+use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::division;
+use crate::space::CodeSpace;
+use crate::usci::{Code01, Code02, Code03, Code04, UsciCode, CODE_CHARS};
+
+// Rough shares of the register: nearly everything is issued by market
+// regulation (9), and most of that to enterprises and individual businesses.
+const DEFAULT_DEPARTMENTS: [(Code01, f64); 6] = [
+  (Code01::C09, 95.0), (Code01::C05, 2.0), (Code01::C01, 1.5), (Code01::C0N, 1.0), (Code01::C08, 0.3), (Code01::C03, 0.2)
+];
+const DEFAULT_CATEGORIES: [(Code02, f64); 3] = [(Code02::L91, 55.0), (Code02::L92, 40.0), (Code02::L93, 5.0)];
+
+// Sampling weights of a realistic register. Departments and categories not
+// listed keep weight 0 and 1, and a region weight multiplies the weight of
+// every prefecture under its prefix.
+#[derive(Debug, Clone)]
+pub struct Weights {
+  departments: HashMap<Code01, f64>,
+  categories: HashMap<Code02, f64>,
+  regions: HashMap<String, f64>,
+  // Share of Code04 allocated in the MA series since 2015, the rest are
+  // legacy numeric organization codes.
+  ma_ratio: f64
+}
+
+impl Weights {
+
+  pub fn new() -> Self {
+    return Weights { departments: HashMap::new(), categories: HashMap::new(), regions: HashMap::new(), ma_ratio: 0.8 };
+  }
+
+  pub fn department(&mut self, code01: Code01, weight: f64) {
+    self.departments.insert(code01, weight);
+  }
+
+  pub fn category(&mut self, code02: Code02, weight: f64) {
+    self.categories.insert(code02, weight);
+  }
+
+  // Prefix of 2 to 4 digits, such as a province or a prefecture.
+  pub fn region(&mut self, prefix: &str, weight: f64) {
+    self.regions.insert(prefix.to_string(), weight);
+  }
+
+  pub fn ma_ratio(&mut self, ratio: f64) {
+    self.ma_ratio = ratio.clamp(0.0, 1.0);
+  }
+
+  // One "department 9 95", "category 91 55", "region 4403 3" or "ma 0.8"
+  // per line, # starts a comment. Entries override the defaults.
+  pub fn from_reader<R: BufRead>(reader: R) -> UsciResult<Self> {
+    let mut weights = Weights::default();
+    for line in reader.lines() {
+      let line = line?;
+      let content = line.split('#').next().unwrap().trim();
+      if content.is_empty() {
+        continue;
+      }
+      let fields: Vec<&str> = content.split_whitespace().collect();
+      let parsed = match fields.as_slice() {
+        [key, value] => value.parse::<f64>().ok().filter(|value| value.is_finite() && *value >= 0.0).map(|value| (*key, "", value)),
+        [key, code, value] => value.parse::<f64>().ok().filter(|value| value.is_finite() && *value >= 0.0).map(|value| (*key, *code, value)),
+        _ => None
+      };
+      let applied = match parsed {
+        Some(("department", code, weight)) => Code01::from_str(code).map(|code01| weights.department(code01, weight)).is_ok(),
+        Some(("category", code, weight)) => Code02::from_str(code).map(|code02| weights.category(code02, weight)).is_ok(),
+        // Only county level would tell codes under one prefecture apart.
+        Some(("region", code, weight)) if (2..=4).contains(&code.len()) && division::is_known(code) => {
+          weights.region(code, weight);
+          true
+        }
+        Some(("ma", "", ratio)) if ratio <= 1.0 => {
+          weights.ma_ratio(ratio);
+          true
+        }
+        _ => false
+      };
+      if !applied {
+        return Err(UsciError::CommonError(format!("Parse Weights Error: {}", line)));
+      }
+    }
+    return Ok(weights);
+  }
+
+  fn prefecture_weight(&self, prefecture: &str) -> f64 {
+    return self.regions.iter()
+      .filter(|(prefix, _)| prefecture.starts_with(prefix.as_str()))
+      .map(|(_, weight)| weight)
+      .product();
+  }
+
+}
+
+impl Default for Weights {
+
+  fn default() -> Self {
+    let mut weights = Weights::new();
+    for (code01, weight) in DEFAULT_DEPARTMENTS.iter() {
+      weights.department(*code01, *weight);
+    }
+    for (code02, weight) in DEFAULT_CATEGORIES.iter() {
+      weights.category(*code02, *weight);
+    }
+    return weights;
+  }
+}
+
+// Codes shaped like production data: weighted departments and categories,
+// regions from the bundled divisions, and Code04 that is either an MA series
+// or a legacy numeric organization code, each with its GB 11714 check char.
+#[derive(Debug, Clone)]
+pub struct RealisticGenerator {
+  departments: Vec<Code01>,
+  department_index: WeightedIndex<f64>,
+  categories: HashMap<Code01, (Vec<Code02>, WeightedIndex<f64>)>,
+  prefectures: Vec<&'static str>,
+  prefecture_index: WeightedIndex<f64>,
+  // Code03 prefix longer than a prefecture, kept as is.
+  region: String,
+  ma_ratio: f64
+}
+
+impl RealisticGenerator {
+
+  pub fn new(weights: &Weights) -> UsciResult<Self> {
+    return RealisticGenerator::within(weights, &CodeSpace::new());
+  }
+
+  // Only codes of the space: its categories keep their weights, and regions
+  // come from the known prefectures under its region prefix. Departments the
+  // space was narrowed to count equally when all of them have weight 0.
+  pub fn within(weights: &Weights, space: &CodeSpace) -> UsciResult<Self> {
+    let weight_error = |name: &str| UsciError::CommonError(format!("Weights Error: every {} has weight 0", name));
+    let departments: Vec<Code01> = Code01::ALL.iter().copied()
+      .filter(|code01| space.categories().iter().any(|code02| code02.get_code01() == *code01))
+      .collect();
+    let mut department_weights: Vec<f64> = departments.iter().map(|code01| weights.departments.get(code01).copied().unwrap_or(0.0)).collect();
+    if space.categories().len() < Code02::ALL.len() && department_weights.iter().all(|weight| *weight == 0.0) {
+      department_weights.fill(1.0);
+    }
+    let department_index = WeightedIndex::new(&department_weights).map_err(|_| weight_error("department"))?;
+    let mut categories = HashMap::new();
+    for (code01, _) in departments.iter().zip(department_weights.iter()).filter(|(_, weight)| **weight > 0.0) {
+      let items: Vec<Code02> = space.categories().iter().copied().filter(|code02| code02.get_code01() == *code01).collect();
+      let index = WeightedIndex::new(items.iter().map(|code02| weights.categories.get(code02).copied().unwrap_or(1.0)))
+        .map_err(|_| UsciError::CommonError(format!("Weights Error: every category of department {} has weight 0", code01)))?;
+      categories.insert(*code01, (items, index));
+    }
+    let region = space.region_prefix();
+    let prefectures: Vec<&'static str> = division::prefectures()
+      .map(|(code, _)| code)
+      .filter(|prefecture| prefecture.starts_with(region) || region.starts_with(prefecture))
+      .collect();
+    if prefectures.is_empty() {
+      return Err(UsciError::CommonError(format!("Parse Code03 Error: no known prefecture under {}", region)));
+    }
+    let prefecture_index = WeightedIndex::new(prefectures.iter().map(|prefecture| weights.prefecture_weight(prefecture)))
+      .map_err(|_| weight_error("region"))?;
+    let region = if region.len() > 4 { region.to_string() } else { String::new() };
+    return Ok(RealisticGenerator { departments, department_index, categories, prefectures, prefecture_index, region, ma_ratio: weights.ma_ratio });
+  }
+
+  fn region<R: Rng + ?Sized>(&self, rng: &mut R) -> Code03 {
+    if !self.region.is_empty() {
+      let suffix: String = (self.region.len()..6).map(|_| CODE_CHARS[rng.gen_range(0..10)]).collect();
+      return Code03::new(&format!("{}{}", self.region, suffix)).unwrap();
+    }
+    let prefecture = self.prefectures[self.prefecture_index.sample(rng)];
+    // County level is not bundled; 00 is the prefecture's own authority.
+    return Code03::new(&format!("{}{:02}", prefecture, rng.gen_range(0..20))).unwrap();
+  }
+
+  fn org_code<R: Rng + ?Sized>(&self, rng: &mut R) -> Code04 {
+    let body: String = if rng.gen_bool(self.ma_ratio) {
+      let serial: String = (0..6).map(|_| CODE_CHARS[rng.gen_range(0..CODE_CHARS.len())]).collect();
+      format!("MA{}", serial)
+    } else {
+      format!("{:08}", rng.gen_range(0..100_000_000))
+    };
+    return Code04::new(&checksum::GB_11714.complete(&body).unwrap()).unwrap();
+  }
+
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> UsciCode {
+    let code01 = self.departments[self.department_index.sample(rng)];
+    let (items, index) = &self.categories[&code01];
+    let code02 = items[index.sample(rng)];
+    return UsciCode::builder()
+      .department(code01)
+      .category(code02)
+      .region(self.region(rng))
+      .org_code(self.org_code(rng))
+      .build()
+      .unwrap();
+  }
+
+}
+
+impl Default for RealisticGenerator {
+
+  fn default() -> Self {
+    return RealisticGenerator::new(&Weights::default()).unwrap();
+  }
+}
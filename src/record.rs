@@ -0,0 +1,131 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::address::{is_placeholder, short_name};
+use crate::common::{UsciError, UsciResult};
+use crate::division;
+use crate::legal_form;
+use crate::taxid::TaxpayerId;
+use crate::usci::UsciCode;
+
+const TRADE_NAMES: [&str; 16] = [
+  "华兴", "瑞丰", "宏达", "鑫源", "恒信", "长青", "金桥", "天润",
+  "博远", "嘉禾", "永盛", "新锐", "海纳", "丰泽", "启明", "安泰"
+];
+const INDUSTRIES: [&str; 10] = ["科技", "贸易", "建筑工程", "餐饮管理", "物流", "信息技术", "农业发展", "文化传媒", "机械制造", "医药"];
+const STREETS: [&str; 10] = ["人民路", "解放路", "中山路", "建设路", "和平路", "新华路", "长江路", "文化路", "胜利路", "朝阳路"];
+
+pub const FIELDS: [&str; 5] = ["code", "name", "address", "org_code", "tax_id"];
+
+// A fake organization whose fields agree with its code: the name ends in a
+// legal form of its category, the address lies in its region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+  pub code: UsciCode,
+  pub name: String,
+  pub address: String,
+  // Code04 as printed on old certificates, 8 chars, a hyphen and the check char.
+  pub org_code: String,
+  pub tax_id: TaxpayerId
+}
+
+impl Record {
+
+  pub fn from_code<R: Rng + ?Sized>(code: UsciCode, rng: &mut R) -> Self {
+    let region = code.code03();
+    let province = division::province(region);
+    let prefecture = division::prefecture(region).filter(|name| !is_placeholder(name) && Some(*name) != province);
+    let city = prefecture.or(province).map(|name| short_name(name).unwrap_or(name)).unwrap_or("");
+    let trade_name = TRADE_NAMES.choose(rng).unwrap();
+    let name = match legal_form::suffixes(&code.code02()).choose(rng).copied() {
+      Some("公司") => format!("{}{}{}有限公司", city, trade_name, INDUSTRIES.choose(rng).unwrap()),
+      Some(suffix @ ("有限合伙" | "普通合伙")) => format!("{}{}投资中心（{}）", city, trade_name, suffix),
+      Some("人民政府") if !city.is_empty() => format!("{}人民政府", city),
+      Some(suffix) => format!("{}{}{}", city, trade_name, suffix),
+      None => format!("{}{}中心", city, trade_name)
+    };
+    let address = format!("{}{}{}{}号", province.unwrap_or(""), prefecture.unwrap_or(""), STREETS.choose(rng).unwrap(), rng.gen_range(1..500));
+    let org_code = format!("{}-{}", &code.code04()[..8], &code.code04()[8..]);
+    return Record { code, name, address, org_code, tax_id: TaxpayerId::from(code) };
+  }
+
+  pub fn values(&self) -> [String; 5] {
+    return [self.code.to_string(), self.name.clone(), self.address.clone(), self.org_code.clone(), self.tax_id.to_string()];
+  }
+
+  pub fn to_json(&self) -> String {
+    let fields: Vec<String> = FIELDS.iter().zip(self.values().iter())
+      .map(|(field, value)| format!("\"{}\": {}", field, json_string(value)))
+      .collect();
+    return format!("{{{}}}", fields.join(", "));
+  }
+
+  pub fn to_csv(&self) -> String {
+    let values: Vec<String> = self.values().iter().map(|value| csv_field(value)).collect();
+    return values.join(",");
+  }
+
+  pub fn to_sql(&self, table: &str) -> UsciResult<String> {
+    if !is_sql_identifier(table) {
+      return Err(UsciError::CommonError(format!("Parse Table Name Error: {}", table)));
+    }
+    let values: Vec<String> = self.values().iter().map(|value| format!("'{}'", value.replace('\'', "''"))).collect();
+    return Ok(format!("INSERT INTO {} ({}) VALUES ({});", table, FIELDS.join(", "), values.join(", ")));
+  }
+
+}
+
+fn json_string(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len() + 2);
+  escaped.push('"');
+  for ch in value.chars() {
+    match ch {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+      ch => escaped.push(ch)
+    }
+  }
+  escaped.push('"');
+  return escaped;
+}
+
+fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n']) {
+    return format!("\"{}\"", value.replace('"', "\"\""));
+  }
+  return value.to_string();
+}
+
+// One CSV row: quoted fields may hold commas and "" for a quote. A row
+// ending inside quotes keeps the rest of the line in its last field.
+pub fn parse_csv_line(line: &str) -> Vec<String> {
+  let mut fields = vec![String::new()];
+  let mut quoted = false;
+  let mut chars = line.chars().peekable();
+  while let Some(ch) = chars.next() {
+    let field = fields.last_mut().unwrap();
+    match ch {
+      '"' if quoted && chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      }
+      '"' if quoted => quoted = false,
+      '"' if field.trim().is_empty() => {
+        field.clear();
+        quoted = true;
+      }
+      ',' if !quoted => fields.push(String::new()),
+      ch => field.push(ch)
+    }
+  }
+  return fields;
+}
+
+// Table names are pasted into the statement, so only plain and
+// schema-qualified identifiers are accepted.
+pub fn is_sql_identifier(table: &str) -> bool {
+  return !table.is_empty() && table.split('.').all(|part| {
+    let mut chars = part.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_') && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+  });
+}
@@ -0,0 +1,199 @@
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::usci::{Code01, Code02, UsciCode, CODE_CHARS};
+
+pub const WILDCARD: u8 = b'?';
+
+#[derive(Debug, Clone)]
+pub struct Solver {
+  pattern: [u8; 18]
+}
+
+impl Solver {
+
+  // A 17-char pattern is a body without its check char, which is then free.
+  pub fn new(pattern: &str) -> UsciResult<Solver> {
+    let bytes = pattern.as_bytes();
+    if !pattern.is_ascii() || (bytes.len() != 17 && bytes.len() != 18) {
+      return Err(UsciError::CommonError(format!("Parse Pattern Error: {}", pattern)));
+    }
+    let mut code = [WILDCARD; 18];
+    code[..bytes.len()].copy_from_slice(bytes);
+    for (index, &byte) in code.iter().enumerate() {
+      if byte != WILDCARD && !Solver::is_valid_at(index, byte) {
+        return Err(UsciError::CommonError(format!("Parse Pattern Error: {} {}", index, byte as char)));
+      }
+    }
+    return Ok(Solver { pattern: code });
+  }
+
+  fn is_valid_at(index: usize, byte: u8) -> bool {
+    match index {
+      0 => Code01::from_char(byte as char).is_ok(),
+      2..=7 => byte.is_ascii_digit(),
+      _ => checksum::GB_32100.value_of(byte).is_some()
+    }
+  }
+
+  fn restrict(mut self, offset: usize, known: &str) -> UsciResult<Solver> {
+    for (index, &byte) in known.as_bytes().iter().enumerate() {
+      let position = offset + index;
+      if self.pattern[position] == WILDCARD && Solver::is_valid_at(position, byte) {
+        self.pattern[position] = byte;
+      } else if self.pattern[position] != byte {
+        return Err(UsciError::CommonError(format!("Solve Error: {} conflicts with pattern", known)));
+      }
+    }
+    return Ok(self);
+  }
+
+  // Region is a Code03 prefix such as "44" or "4403".
+  pub fn region(self, region: &str) -> UsciResult<Solver> {
+    if region.len() > 6 || !region.bytes().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse Code03 Error: {}", region)));
+    }
+    return self.restrict(2, region);
+  }
+
+  // Category is a Code01 such as "9" or a Code01 with its Code02 such as "91".
+  pub fn category(self, category: &str) -> UsciResult<Solver> {
+    if category.is_empty() || category.len() > 2 {
+      return Err(UsciError::CommonError(format!("Parse Code02 Error: {}", category)));
+    }
+    return self.restrict(0, category);
+  }
+
+  pub fn iter(&self) -> Solutions {
+    let pairs: Vec<[u8; 2]> = Code02::ALL.iter()
+      .map(|code02| [code02.get_code01().get_code() as u8, code02.get_code() as u8])
+      .filter(|pair| (self.pattern[0] == WILDCARD || self.pattern[0] == pair[0]) && (self.pattern[1] == WILDCARD || self.pattern[1] == pair[1]))
+      .collect();
+    let check = match self.pattern[17] {
+      WILDCARD => None,
+      byte => checksum::GB_32100.value_of(byte)
+    };
+    let unknowns: Vec<usize> = (2..17).filter(|&index| self.pattern[index] == WILDCARD).collect();
+    // With a known check char the last unknown is derived from the others
+    // instead of enumerated; prefer one in Code04, where every residue is valid.
+    let solved = match check {
+      Some(_) => unknowns.iter().rev().find(|&&index| index >= 8).or_else(|| unknowns.last()).copied(),
+      None => None
+    };
+    let free: Vec<(usize, usize)> = unknowns.iter()
+      .filter(|&&index| Some(index) != solved)
+      .map(|&index| (index, if index < 8 { 10 } else { CODE_CHARS.len() }))
+      .collect();
+    return Solutions {
+      code: self.pattern,
+      done: pairs.is_empty(),
+      pairs,
+      counters: vec![0; free.len()],
+      free,
+      solved,
+      check,
+      pair_index: 0
+    };
+  }
+
+  pub fn count(&self) -> u128 {
+    return self.known_count().unwrap_or_else(|| self.iter().count() as u128);
+  }
+
+  // Count without enumerating, None when only trying every candidate tells,
+  // as when the derived unknown falls in Code03 where a residue may not be a digit.
+  pub fn known_count(&self) -> Option<u128> {
+    let solutions = self.iter();
+    match (solutions.check, solutions.solved) {
+      (None, _) => Some(solutions.size()),
+      (Some(_), Some(solved)) if solved >= 8 => Some(solutions.size()),
+      _ => None
+    }
+  }
+
+}
+
+impl IntoIterator for &Solver {
+  type Item = UsciCode;
+  type IntoIter = Solutions;
+
+  fn into_iter(self) -> Solutions {
+    self.iter()
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solutions {
+  code: [u8; 18],
+  pairs: Vec<[u8; 2]>,
+  free: Vec<(usize, usize)>,
+  solved: Option<usize>,
+  check: Option<usize>,
+  counters: Vec<usize>,
+  pair_index: usize,
+  done: bool
+}
+
+impl Solutions {
+
+  // Number of combinations left to try, not all of them need to be valid.
+  fn size(&self) -> u128 {
+    if self.done {
+      return 0;
+    }
+    return self.free.iter().fold(self.pairs.len() as u128, |size, &(_, radix)| size * radix as u128);
+  }
+
+  fn candidate(&mut self) -> Option<UsciCode> {
+    self.code[..2].copy_from_slice(&self.pairs[self.pair_index]);
+    for (&(index, _), &counter) in self.free.iter().zip(self.counters.iter()) {
+      self.code[index] = CODE_CHARS[counter] as u8;
+    }
+    if let (Some(index), Some(check)) = (self.solved, self.check) {
+      self.code[index] = b'0';
+      let modulus = checksum::GB_32100.modulus();
+      let weight = checksum::GB_32100.weights().unwrap()[index];
+      let partial = checksum::GB_32100.compute_value(&self.code[..17]).ok()?;
+      let inverse = (1..modulus).find(|inverse| weight * inverse % modulus == 1).unwrap();
+      let value = (partial + modulus - check) * inverse % modulus;
+      if index < 8 && value >= 10 {
+        return None;
+      }
+      self.code[index] = CODE_CHARS[value] as u8;
+    }
+    if self.check.is_none() {
+      let value = checksum::GB_32100.compute_value(&self.code[..17]).ok()?;
+      self.code[17] = CODE_CHARS[value] as u8;
+    }
+    return UsciCode::from_bytes(&self.code).ok();
+  }
+
+  fn advance(&mut self) {
+    for (counter, &(_, radix)) in self.counters.iter_mut().zip(self.free.iter()).rev() {
+      *counter += 1;
+      if *counter < radix {
+        return;
+      }
+      *counter = 0;
+    }
+    self.pair_index += 1;
+    if self.pair_index == self.pairs.len() {
+      self.done = true;
+    }
+  }
+
+}
+
+impl Iterator for Solutions {
+  type Item = UsciCode;
+
+  fn next(&mut self) -> Option<UsciCode> {
+    while !self.done {
+      let candidate = self.candidate();
+      self.advance();
+      if candidate.is_some() {
+        return candidate;
+      }
+    }
+    return None;
+  }
+}
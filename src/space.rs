@@ -0,0 +1,187 @@
+use rand::Rng;
+use crate::common::{UsciError, UsciResult};
+use crate::usci::{Code02, Code03, Code04, UsciCode};
+
+// Every valid code with the given categories and a Code03 under the given
+// prefix. The check char is determined by the rest, so it adds nothing to
+// the size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSpace {
+  categories: Vec<Code02>,
+  region: String
+}
+
+impl CodeSpace {
+
+  pub fn new() -> Self {
+    return CodeSpace { categories: Code02::ALL.to_vec(), region: String::new() };
+  }
+
+  // Region is a Code03 prefix such as "44" or "4403".
+  pub fn region(mut self, region: &str) -> UsciResult<Self> {
+    if region.len() > 6 || !region.bytes().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse Code03 Error: {}", region)));
+    }
+    self.region = region.to_string();
+    return Ok(self);
+  }
+
+  // Category is a Code01 such as "9" or a Code01 with its Code02 such as "91".
+  pub fn category(mut self, category: &str) -> UsciResult<Self> {
+    let chars: Vec<char> = category.chars().collect();
+    let matches = |code02: &Code02| match chars.as_slice() {
+      [code01] => code02.get_code01().get_code() == *code01,
+      [code01, code] => code02.get_code01().get_code() == *code01 && code02.get_code() == *code,
+      _ => false
+    };
+    self.categories.retain(matches);
+    if self.categories.is_empty() {
+      return Err(UsciError::CommonError(format!("Parse Code02 Error: {}", category)));
+    }
+    return Ok(self);
+  }
+
+  pub fn categories(&self) -> &[Code02] {
+    return &self.categories;
+  }
+
+  pub fn region_prefix(&self) -> &str {
+    return &self.region;
+  }
+
+  pub fn regions(&self) -> u128 {
+    return 10u128.pow(6 - self.region.len() as u32);
+  }
+
+  pub fn org_codes() -> u128 {
+    return 31u128.pow(9);
+  }
+
+  pub fn size(&self) -> u128 {
+    return self.categories.len() as u128 * self.regions() * CodeSpace::org_codes();
+  }
+
+  pub fn contains(&self, code: &UsciCode) -> bool {
+    return self.categories.contains(&code.code02()) && code.code03().starts_with(self.region.as_str());
+  }
+
+  fn region_at(&self, index: u128) -> Code03 {
+    let width = 6 - self.region.len();
+    let suffix = if width == 0 { String::new() } else { format!("{:0width$}", index, width = width) };
+    return Code03::new(&format!("{}{}", self.region, suffix)).unwrap();
+  }
+
+  // Codes are numbered in text order: category, then region, then Code04
+  // read as a base-31 number.
+  pub fn rank(&self, code: &UsciCode) -> Option<u128> {
+    if !self.contains(code) {
+      return None;
+    }
+    let category = self.categories.iter().position(|code02| *code02 == code.code02()).unwrap() as u128;
+    let region: u128 = code.code03()[self.region.len()..].parse().unwrap_or(0);
+    return Some((category * self.regions() + region) * CodeSpace::org_codes() + code.org_code().index());
+  }
+
+  pub fn unrank(&self, index: u128) -> UsciResult<UsciCode> {
+    if index >= self.size() {
+      return Err(UsciError::CommonError(format!("Unrank Error: {} is beyond the {} codes of the space", index, self.size())));
+    }
+    let rest = index / CodeSpace::org_codes();
+    let code02 = self.categories[(rest / self.regions()) as usize];
+    return UsciCode::builder()
+      .department(code02.get_code01())
+      .category(code02)
+      .region(self.region_at(rest % self.regions()))
+      .org_code(Code04::from_index(index % CodeSpace::org_codes()))
+      .build();
+  }
+
+  pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UsciCode {
+    let code02 = self.categories[rng.gen_range(0..self.categories.len())];
+    let code03 = self.region_at(rng.gen_range(0..self.regions()));
+    return UsciCode::builder()
+      .department(code02.get_code01())
+      .category(code02)
+      .region(code03)
+      .org_code(Code04::from_random(rng))
+      .build()
+      .unwrap();
+  }
+
+}
+
+impl Default for CodeSpace {
+
+  fn default() -> Self {
+    return CodeSpace::new();
+  }
+}
+
+// SplitMix64 finalizer, enough to decorrelate neighbouring indices; the
+// permutation hides the order of a space, it is not meant to be secret.
+fn mix(mut value: u64) -> u64 {
+  value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+  value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+  return value ^ (value >> 31);
+}
+
+const ROUNDS: u64 = 6;
+
+// Keyed bijection on 0..size: a balanced Feistel network on the smallest
+// even number of bits covering size, walking the cycle until the result
+// falls back into range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permutation {
+  key: u64,
+  size: u128,
+  half_bits: u32
+}
+
+impl Permutation {
+
+  pub fn new(key: u64, size: u128) -> Self {
+    let bits = 128 - size.saturating_sub(1).leading_zeros();
+    return Permutation { key, size, half_bits: bits.div_ceil(2).max(1) };
+  }
+
+  fn round(&self, round: u64, value: u128) -> u128 {
+    let mask = (1u128 << self.half_bits) - 1;
+    return mix(value as u64 ^ mix(self.key ^ round)) as u128 & mask;
+  }
+
+  fn encrypt(&self, value: u128) -> u128 {
+    let mask = (1u128 << self.half_bits) - 1;
+    let (mut left, mut right) = (value >> self.half_bits, value & mask);
+    for round in 0..ROUNDS {
+      (left, right) = (right, left ^ self.round(round, right));
+    }
+    return (left << self.half_bits) | right;
+  }
+
+  fn decrypt(&self, value: u128) -> u128 {
+    let mask = (1u128 << self.half_bits) - 1;
+    let (mut left, mut right) = (value >> self.half_bits, value & mask);
+    for round in (0..ROUNDS).rev() {
+      (left, right) = (right ^ self.round(round, left), left);
+    }
+    return (left << self.half_bits) | right;
+  }
+
+  // Both directions expect an index below size.
+  pub fn permute(&self, index: u128) -> u128 {
+    let mut value = self.encrypt(index);
+    while value >= self.size {
+      value = self.encrypt(value);
+    }
+    return value;
+  }
+
+  pub fn invert(&self, index: u128) -> u128 {
+    let mut value = self.decrypt(index);
+    while value >= self.size {
+      value = self.decrypt(value);
+    }
+    return value;
+  }
+
+}
@@ -0,0 +1,58 @@
+use std::str::FromStr;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+use crate::usci::{PackedUsciCode, UsciCode};
+
+// A UsciCode column is TEXT and a PackedUsciCode column holds the nine
+// packed bytes, for any database sqlx supports. Both are parsed again on
+// every read, so a row holding an invalid code fails to decode.
+impl<DB: Database> Type<DB> for UsciCode where str: Type<DB> {
+
+  fn type_info() -> DB::TypeInfo {
+    return <str as Type<DB>>::type_info();
+  }
+
+  fn compatible(ty: &DB::TypeInfo) -> bool {
+    return <str as Type<DB>>::compatible(ty);
+  }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for UsciCode where String: Encode<'q, DB> {
+
+  fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+    return self.to_string().encode_by_ref(buf);
+  }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for UsciCode where &'r str: Decode<'r, DB> {
+
+  fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+    return Ok(UsciCode::from_str(<&str as Decode<DB>>::decode(value)?)?);
+  }
+}
+
+impl<DB: Database> Type<DB> for PackedUsciCode where [u8]: Type<DB> {
+
+  fn type_info() -> DB::TypeInfo {
+    return <[u8] as Type<DB>>::type_info();
+  }
+
+  fn compatible(ty: &DB::TypeInfo) -> bool {
+    return <[u8] as Type<DB>>::compatible(ty);
+  }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for PackedUsciCode where Vec<u8>: Encode<'q, DB> {
+
+  fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+    return self.as_bytes().to_vec().encode_by_ref(buf);
+  }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for PackedUsciCode where &'r [u8]: Decode<'r, DB> {
+
+  fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+    return Ok(PackedUsciCode::from_bytes(<&[u8] as Decode<DB>>::decode(value)?)?);
+  }
+}
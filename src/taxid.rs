@@ -0,0 +1,187 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::checksum;
+use crate::common::{UsciError, UsciResult};
+use crate::division;
+use crate::homoglyph;
+use crate::idcard::{mask, ResidentId};
+use crate::usci::{Code04, UsciCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaxpayerForm {
+  // 18-char usci code
+  Usci,
+  // 15 chars: 6-digit region and 9-char organization code
+  Legacy,
+  // 18-digit resident ID number
+  ResidentId,
+  // 15-digit resident ID number followed by a 2-digit sequence
+  LegacyResidentIdWithSuffix,
+  // 18-digit resident ID number followed by a 2-digit sequence
+  ResidentIdWithSuffix
+}
+
+impl TaxpayerForm {
+
+  pub fn get_type(&self) -> &'static str {
+    match self {
+      TaxpayerForm::Usci => "统一社会信用代码",
+      TaxpayerForm::Legacy => "行政区划码+组织机构代码",
+      TaxpayerForm::ResidentId => "居民身份证号码",
+      TaxpayerForm::LegacyResidentIdWithSuffix => "15位居民身份证号码+2位顺序码",
+      TaxpayerForm::ResidentIdWithSuffix => "18位居民身份证号码+2位顺序码"
+    }
+  }
+
+  // Forms that carry a birth date.
+  pub fn is_personal(&self) -> bool {
+    return matches!(self, TaxpayerForm::ResidentId | TaxpayerForm::LegacyResidentIdWithSuffix | TaxpayerForm::ResidentIdWithSuffix);
+  }
+
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaxpayerId {
+  code: String,
+  form: TaxpayerForm
+}
+
+impl TaxpayerId {
+
+  pub fn code_type() -> &'static str {
+    return "纳税人识别号";
+  }
+
+  pub fn form(&self) -> TaxpayerForm {
+    return self.form;
+  }
+
+  pub fn as_str(&self) -> &str {
+    return self.code.as_str();
+  }
+
+  pub fn usci(&self) -> Option<UsciCode> {
+    match self.form {
+      TaxpayerForm::Usci => UsciCode::from_str(&self.code).ok(),
+      _ => None
+    }
+  }
+
+  pub fn resident_id(&self) -> Option<ResidentId> {
+    match self.form {
+      TaxpayerForm::ResidentId => ResidentId::from_str(&self.code).ok(),
+      TaxpayerForm::LegacyResidentIdWithSuffix => ResidentId::from_legacy(&self.code[..15]).ok(),
+      TaxpayerForm::ResidentIdWithSuffix => ResidentId::from_str(&self.code[..18]).ok(),
+      _ => None
+    }
+  }
+
+  pub fn masked(&self) -> String {
+    if self.form.is_personal() {
+      return mask(&self.code);
+    }
+    return self.code.clone();
+  }
+
+  fn parse_legacy(code: &str) -> UsciResult<()> {
+    let region = &code[..6];
+    // As for ID numbers, old prefecture codes stay in use after a change.
+    if !region.bytes().all(|byte| byte.is_ascii_digit()) || division::province(region).is_none() {
+      return Err(UsciError::CommonError(format!("Parse Tax ID Region Error: {}", region)));
+    }
+    let org_code = Code04::new(&code[6..])?;
+    if !checksum::GB_11714.verify(org_code.as_str()) {
+      return Err(UsciError::CommonError(format!("Verify Tax ID Organization Code Error: {}", org_code)));
+    }
+    return Ok(());
+  }
+
+  fn parse_suffix(suffix: &str) -> UsciResult<()> {
+    if !suffix.bytes().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse Tax ID Suffix Error: {}", suffix)));
+    }
+    return Ok(());
+  }
+
+}
+
+// A rejected input is masked only when it has the shape of an ID card based
+// form, digits up to the check char; other numbers hold no birth date.
+pub fn mask_rejected(code_str: &str) -> String {
+  let digits = |end: usize| code_str.get(..end).is_some_and(|prefix| prefix.bytes().all(|byte| byte.is_ascii_digit()));
+  let personal = match code_str.len() {
+    17 => digits(15),
+    18 | 20 => digits(17),
+    _ => false
+  };
+  if personal {
+    return mask(code_str);
+  }
+  return code_str.to_string();
+}
+
+impl FromStr for TaxpayerId {
+  type Err = UsciError;
+
+  fn from_str(code_str: &str) -> UsciResult<Self> {
+    let code = code_str.trim().to_ascii_uppercase();
+    if !code.is_ascii() {
+      let spoofed = homoglyph::find_spoofed(&code);
+      if !spoofed.is_empty() {
+        return Err(UsciError::SpoofedCharacter(spoofed));
+      }
+      return Err(UsciError::CommonError(format!("Parse Tax ID Error: {}", code_str)));
+    }
+    let form = match code.len() {
+      18 => {
+        match UsciCode::from_str(&code) {
+          Ok(_) => TaxpayerForm::Usci,
+          Err(err) => {
+            if !code[..17].bytes().all(|byte| byte.is_ascii_digit()) {
+              return Err(err);
+            }
+            ResidentId::from_str(&code)?;
+            TaxpayerForm::ResidentId
+          }
+        }
+      }
+      15 => {
+        TaxpayerId::parse_legacy(&code)?;
+        TaxpayerForm::Legacy
+      }
+      17 => {
+        ResidentId::from_legacy(&code[..15])?;
+        TaxpayerId::parse_suffix(&code[15..])?;
+        TaxpayerForm::LegacyResidentIdWithSuffix
+      }
+      20 => {
+        ResidentId::from_str(&code[..18])?;
+        TaxpayerId::parse_suffix(&code[18..])?;
+        TaxpayerForm::ResidentIdWithSuffix
+      }
+      _ => return Err(UsciError::CommonError(format!("Parse Tax ID Error: {}", code_str)))
+    };
+    return Ok(TaxpayerId { code, form });
+  }
+}
+
+impl From<UsciCode> for TaxpayerId {
+
+  fn from(code: UsciCode) -> Self {
+    return TaxpayerId { code: code.to_string(), form: TaxpayerForm::Usci };
+  }
+}
+
+impl Display for TaxpayerId {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.as_str())
+  }
+}
+
+impl AsRef<str> for TaxpayerId {
+
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
@@ -1,9 +1,16 @@
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use rand::Rng;
-use rand::rngs::ThreadRng;
+use crate::checksum;
 use crate::common::{UsciError, UsciResult};
+use crate::division;
+use crate::homoglyph;
+use crate::homoglyph::SpoofedChar;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Code01 {
   C01,
   C02,
@@ -21,11 +28,16 @@ pub enum Code01 {
 
 impl Code01 {
 
-  pub(crate) fn code_type() -> &'static str {
+  pub const ALL: [Code01; 12] = [
+    Code01::C01, Code01::C02, Code01::C03, Code01::C04, Code01::C05, Code01::C06,
+    Code01::C07, Code01::C08, Code01::C09, Code01::C0A, Code01::C0N, Code01::C0Y
+  ];
+
+  pub fn code_type() -> &'static str {
     return "登记管理部门";
   }
 
-  pub(crate) fn get_code(&self) -> char {
+  pub fn get_code(&self) -> char {
     match self {
       Code01::C01 => '1',
       Code01::C02 => '2',
@@ -42,7 +54,7 @@ impl Code01 {
     }
   }
 
-  pub(crate) fn get_type(&self) -> &'static str {
+  pub fn get_type(&self) -> &'static str {
     match self {
       Code01::C01 => "机构编制",
       Code01::C02 => "外交",
@@ -77,9 +89,8 @@ impl Code01 {
     }
   }
 
-  pub fn from_random(rng: &mut ThreadRng) -> Self {
-    let random = rng.gen::<usize>() % 12;
-    match random {
+  pub fn from_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    match rng.gen_range(0..12) {
       0 => Code01::C01,
       1 => Code01::C02,
       2 => Code01::C03,
@@ -98,7 +109,26 @@ impl Code01 {
 
 }
 
-#[derive(Debug)]
+impl Display for Code01 {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.get_code().encode_utf8(&mut [0; 4]))
+  }
+}
+
+impl FromStr for Code01 {
+  type Err = UsciError;
+
+  fn from_str(code: &str) -> UsciResult<Self> {
+    let mut code_chars = code.chars();
+    match (code_chars.next(), code_chars.next()) {
+      (Some(ch), None) => Code01::from_char(ch),
+      _ => Err(UsciError::CommonError(format!("Parse Code01 Error: {}", code)))
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Code02 {
   D11,
   D12,
@@ -140,11 +170,43 @@ pub enum Code02 {
 
 impl Code02 {
 
-  pub(crate) fn code_type() -> &'static str {
+  pub const ALL: [Code02; 36] = [
+    Code02::D11, Code02::D12, Code02::D13, Code02::D19,
+    Code02::E21, Code02::E29,
+    Code02::F31, Code02::F32, Code02::F33, Code02::F34, Code02::F35, Code02::F39,
+    Code02::G41, Code02::G49,
+    Code02::H51, Code02::H52, Code02::H53, Code02::H59,
+    Code02::I61, Code02::I62, Code02::I69,
+    Code02::J71, Code02::J72, Code02::J79,
+    Code02::K81, Code02::K89,
+    Code02::L91, Code02::L92, Code02::L93,
+    Code02::MA1, Code02::MA9,
+    Code02::NN1, Code02::NN2, Code02::NN3, Code02::NN9,
+    Code02::P01
+  ];
+
+  pub fn code_type() -> &'static str {
     return "机构类别";
   }
 
-  pub(crate) fn get_code(&self) -> char {
+  pub fn get_code01(&self) -> Code01 {
+    match self {
+      Code02::D11 | Code02::D12 | Code02::D13 | Code02::D19 => Code01::C01,
+      Code02::E21 | Code02::E29 => Code01::C02,
+      Code02::F31 | Code02::F32 | Code02::F33 | Code02::F34 | Code02::F35 | Code02::F39 => Code01::C03,
+      Code02::G41 | Code02::G49 => Code01::C04,
+      Code02::H51 | Code02::H52 | Code02::H53 | Code02::H59 => Code01::C05,
+      Code02::I61 | Code02::I62 | Code02::I69 => Code01::C06,
+      Code02::J71 | Code02::J72 | Code02::J79 => Code01::C07,
+      Code02::K81 | Code02::K89 => Code01::C08,
+      Code02::L91 | Code02::L92 | Code02::L93 => Code01::C09,
+      Code02::MA1 | Code02::MA9 => Code01::C0A,
+      Code02::NN1 | Code02::NN2 | Code02::NN3 | Code02::NN9 => Code01::C0N,
+      Code02::P01 => Code01::C0Y,
+    }
+  }
+
+  pub fn get_code(&self) -> char {
     match self {
       Code02::D11 => '1',
       Code02::D12 => '2',
@@ -174,7 +236,7 @@ impl Code02 {
       Code02::K89 => '9',
       Code02::L91 => '1',
       Code02::L92 => '2',
-      Code02::L93 => '9',
+      Code02::L93 => '3',
       Code02::MA1 => '1',
       Code02::MA9 => '9',
       Code02::NN1 => '1',
@@ -185,7 +247,7 @@ impl Code02 {
     }
   }
 
-  pub(crate) fn get_type(&self) -> &'static str {
+  pub fn get_type(&self) -> &'static str {
     match self {
       Code02::D11 => "机关",
       Code02::D12 => "事业单位",
@@ -240,7 +302,7 @@ impl Code02 {
       Code01::C02 => {
         match ch {
           '1' => Ok(Code02::E21),
-          '2' => Ok(Code02::E29),
+          '9' => Ok(Code02::E29),
           _ => Err(UsciError::CommonError(format!("Parse Code02 Error (Code01: {}): {}", code01.get_code(), ch)))
         }
       }
@@ -327,11 +389,10 @@ impl Code02 {
     }
   }
 
-  pub fn from_random(code01: &Code01, rng: &mut ThreadRng) -> Self {
-    let random = rng.gen::<usize>();
+  pub fn from_random<R: Rng + ?Sized>(code01: &Code01, rng: &mut R) -> Self {
     match code01 {
       Code01::C01 => {
-        let number = random % 4;
+        let number = rng.gen_range(0..4);
         match number {
           0 => Code02::D11,
           1 => Code02::D12,
@@ -341,7 +402,7 @@ impl Code02 {
         }
       }
       Code01::C02 => {
-        let number = random % 2;
+        let number = rng.gen_range(0..2);
         match number {
           0 => Code02::E21,
           1 => Code02::E29,
@@ -349,7 +410,7 @@ impl Code02 {
         }
       }
       Code01::C03 => {
-        let number = random % 6;
+        let number = rng.gen_range(0..6);
         match number {
           0 => Code02::F31,
           1 => Code02::F32,
@@ -361,7 +422,7 @@ impl Code02 {
         }
       }
       Code01::C04 => {
-        let number = random % 2;
+        let number = rng.gen_range(0..2);
         match number {
           0 => Code02::G41,
           1 => Code02::G49,
@@ -369,7 +430,7 @@ impl Code02 {
         }
       }
       Code01::C05 => {
-        let number = random % 4;
+        let number = rng.gen_range(0..4);
         match number {
           0 => Code02::H51,
           1 => Code02::H52,
@@ -379,7 +440,7 @@ impl Code02 {
         }
       }
       Code01::C06 => {
-        let number = random % 3;
+        let number = rng.gen_range(0..3);
         match number {
           0 => Code02::I61,
           1 => Code02::I62,
@@ -388,7 +449,7 @@ impl Code02 {
         }
       }
       Code01::C07 => {
-        let number = random % 3;
+        let number = rng.gen_range(0..3);
         match number {
           0 => Code02::J71,
           1 => Code02::J72,
@@ -397,7 +458,7 @@ impl Code02 {
         }
       }
       Code01::C08 => {
-        let number = random % 2;
+        let number = rng.gen_range(0..2);
         match number {
           0 => Code02::K81,
           1 => Code02::K89,
@@ -405,7 +466,7 @@ impl Code02 {
         }
       }
       Code01::C09 => {
-        let number = random % 3;
+        let number = rng.gen_range(0..3);
         match number {
           0 => Code02::L91,
           1 => Code02::L92,
@@ -414,7 +475,7 @@ impl Code02 {
         }
       }
       Code01::C0A => {
-        let number = random % 2;
+        let number = rng.gen_range(0..2);
         match number {
           0 => Code02::MA1,
           1 => Code02::MA9,
@@ -422,7 +483,7 @@ impl Code02 {
         }
       }
       Code01::C0N => {
-        let number = random % 4;
+        let number = rng.gen_range(0..4);
         match number {
           0 => Code02::NN1,
           1 => Code02::NN2,
@@ -438,60 +499,123 @@ impl Code02 {
   }
 }
 
-const CODE03_CHARS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+// Written with its Code01, e.g. "91", the same form FromStr reads.
+impl Display for Code02 {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(&format!("{}{}", self.get_code01().get_code(), self.get_code()))
+  }
+}
+
+impl FromStr for Code02 {
+  type Err = UsciError;
+
+  // Code02 is only meaningful together with its Code01, so it is parsed from
+  // the two leading characters of a code, e.g. "91".
+  fn from_str(code: &str) -> UsciResult<Self> {
+    let mut code_chars = code.chars();
+    match (code_chars.next(), code_chars.next(), code_chars.next()) {
+      (Some(ch01), Some(ch02), None) => Code02::from_char(&Code01::from_char(ch01)?, ch02),
+      _ => Err(UsciError::CommonError(format!("Parse Code02 Error: {}", code)))
+    }
+  }
+}
+
+pub(crate) const CODE03_CHARS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+// No GB/T 2260 province starts with 0, so a Code03 under 00 can never be
+// assigned and marks a code as fictitious.
+pub const TEST_PROVINCE: &str = "00";
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Code03 {
-  code: String
+  code: [u8; 6]
 }
 
 impl Code03 {
 
-  pub(crate) fn code_type() -> &'static str {
+  pub fn code_type() -> &'static str {
     return "登记管理机关行政区划码";
   }
 
   pub fn new(code: &str) -> UsciResult<Code03> {
-    if code.len() != 6 {
-      return Err(UsciError::CommonError(format!("Parse Code03 Error: {}", code)));
-    }
-    let mut code_chars = code.chars();
-    while let Some(ch) = code_chars.next() {
-      if !Code03::is_valid_char(ch) {
-        return Err(UsciError::CommonError(format!("Parse Code03 Error: {}", code)));
-      }
+    return Code03::from_bytes(code.as_bytes());
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> UsciResult<Code03> {
+    let code: [u8; 6] = match bytes.try_into() {
+      Ok(code) => code,
+      Err(_) => return Err(UsciError::CommonError(format!("Parse Code03 Error: {}", String::from_utf8_lossy(bytes))))
+    };
+    if !code.iter().all(|byte| byte.is_ascii_digit()) {
+      return Err(UsciError::CommonError(format!("Parse Code03 Error: {}", String::from_utf8_lossy(bytes))));
     }
-    return Ok(Code03 { code: code.to_string() });
+    return Ok(Code03 { code });
   }
 
   pub fn is_valid_char(ch: char) -> bool {
     return CODE03_CHARS.contains(&ch);
   }
 
-  pub fn from_random(rng: &mut ThreadRng) -> Self {
-    let mut code03_str = String::with_capacity(6);
-    for _ in 0..6 {
-      let ch_index = rng.gen::<usize>() % 10;
-      code03_str.push(CODE03_CHARS.get(ch_index).unwrap().clone());
+  pub fn from_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    let mut code = [0u8; 6];
+    for byte in code.iter_mut() {
+      let ch_index = rng.gen_range(0..10);
+      *byte = CODE03_CHARS[ch_index] as u8;
     }
-    return Code03 { code: code03_str };
+    return Code03 { code };
+  }
+
+  pub fn from_random_test<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    return Code03::new(&format!("{}{:04}", TEST_PROVINCE, rng.gen_range(0..10000))).unwrap();
+  }
+
+  pub fn is_test(&self) -> bool {
+    return self.as_str().starts_with(TEST_PROVINCE);
+  }
+
+  pub fn as_str(&self) -> &str {
+    return std::str::from_utf8(&self.code).unwrap();
+  }
+
+  pub fn province(&self) -> Option<&'static str> {
+    return division::province(self.as_str());
   }
 
+  pub fn prefecture(&self) -> Option<&'static str> {
+    return division::prefecture(self.as_str());
+  }
+
+  pub fn is_known(&self) -> bool {
+    return division::is_known(self.as_str());
+  }
+
+}
+
+impl Display for Code03 {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.as_str())
+  }
 }
 
-impl ToString for Code03 {
-  fn to_string(&self) -> String {
-    self.code.to_string()
+impl FromStr for Code03 {
+  type Err = UsciError;
+
+  fn from_str(code: &str) -> UsciResult<Self> {
+    Code03::new(code)
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Code04 {
-  code: String
+  code: [u8; 9]
 }
 
-const CODE_CHARS: [char; 31] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'L', 'M', 'N', 'P', 'Q', 'R', 'T', 'U', 'W', 'X', 'Y'];
-const CODE_WEIGHT: [usize; 17] = [1, 3, 9, 27, 19, 26, 16, 17, 20, 29, 25, 13, 8, 24, 10, 30, 28];
+pub(crate) const CODE_CHARS: [char; 31] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'L', 'M', 'N', 'P', 'Q', 'R', 'T', 'U', 'W', 'X', 'Y'];
+
+fn code_value(byte: u8) -> Option<usize> {
+  return checksum::GB_32100.value_of(byte);
+}
 
 impl Code04 {
 
@@ -500,123 +624,321 @@ impl Code04 {
   }
 
   pub fn new(code: &str) -> UsciResult<Code04> {
-    if code.len() != 9 {
-      return Err(UsciError::CommonError(format!("Parse Code04 Error: {}", code)));
-    }
-    let mut code_chars = code.chars();
-    while let Some(ch) = code_chars.next() {
-      if !Code04::is_valid_char(ch) {
-        return Err(UsciError::CommonError(format!("Parse Code04 Error: {}", code)));
-      }
+    return Code04::from_bytes(code.as_bytes());
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> UsciResult<Code04> {
+    let code: [u8; 9] = match bytes.try_into() {
+      Ok(code) => code,
+      Err(_) => return Err(UsciError::CommonError(format!("Parse Code04 Error: {}", String::from_utf8_lossy(bytes))))
+    };
+    if !code.iter().all(|&byte| code_value(byte).is_some()) {
+      return Err(UsciError::CommonError(format!("Parse Code04 Error: {}", String::from_utf8_lossy(bytes))));
     }
-    return Ok(Code04 { code: code.to_string() });
+    return Ok(Code04 { code });
   }
 
   pub fn is_valid_char(ch: char) -> bool {
     return CODE_CHARS.contains(&ch);
   }
 
-  pub fn from_random(rng: &mut ThreadRng) -> Self {
-    let mut code04_str = String::with_capacity(9);
-    for _ in 0..9 {
-      let ch_index = rng.gen::<usize>() % 31;
-      code04_str.push(CODE_CHARS.get(ch_index).unwrap().clone())
+  pub fn from_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    let mut code = [0u8; 9];
+    for byte in code.iter_mut() {
+      let ch_index = rng.gen_range(0..31);
+      *byte = CODE_CHARS[ch_index] as u8;
     }
-    return Code04 { code: code04_str };
+    return Code04 { code };
+  }
+
+  // The index-th code in text order, reading the nine chars as a base-31
+  // number; wraps around past 31^9.
+  pub(crate) fn from_index(mut index: u128) -> Self {
+    let mut code = [0u8; 9];
+    for byte in code.iter_mut().rev() {
+      *byte = CODE_CHARS[(index % 31) as usize] as u8;
+      index /= 31;
+    }
+    return Code04 { code };
+  }
+
+  pub(crate) fn index(&self) -> u128 {
+    return self.code.iter().fold(0u128, |index, &byte| index * 31 + code_value(byte).unwrap() as u128);
+  }
+
+  pub fn as_str(&self) -> &str {
+    return std::str::from_utf8(&self.code).unwrap();
+  }
+
+  // The last char of an organization code is its own GB 11714 check char.
+  pub fn is_checksum_valid(&self) -> bool {
+    return checksum::GB_11714.verify(self.as_str());
   }
 
 }
 
-impl ToString for Code04 {
+impl Display for Code04 {
 
-  fn to_string(&self) -> String {
-    self.code.to_string()
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.as_str())
   }
 }
 
-#[derive(Debug)]
+impl FromStr for Code04 {
+  type Err = UsciError;
+
+  fn from_str(code: &str) -> UsciResult<Self> {
+    Code04::new(code)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Code05 {
   code: char
 }
 
 impl Code05 {
 
-  pub(crate) fn code_type() -> &'static str {
+  pub fn code_type() -> &'static str {
     return "校验码";
   }
 
+  pub fn get_code(&self) -> char {
+    return self.code;
+  }
+
 }
 
-impl ToString for Code05 {
+impl Display for Code05 {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.code.encode_utf8(&mut [0; 4]))
+  }
+}
 
-  fn to_string(&self) -> String {
-    self.code.to_string()
+impl FromStr for Code05 {
+  type Err = UsciError;
 
+  fn from_str(code: &str) -> UsciResult<Self> {
+    let mut code_chars = code.chars();
+    match (code_chars.next(), code_chars.next()) {
+      (Some(ch), None) if CODE_CHARS.contains(&ch) => Ok(Code05 { code: ch }),
+      _ => Err(UsciError::CommonError(format!("Parse Code05 Error: {}", code)))
+    }
   }
 }
 
-#[derive(Debug)]
+// 36 * 10^6 * 31^9 < 2^72, so a packed code fits in nine big-endian bytes.
+pub const PACKED_BYTES: usize = 9;
+
+// A code is stored as its 18 validated ASCII bytes; the segments are decoded
+// or borrowed from them on access, so parsing and copying never allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
 pub struct UsciCode {
-  pub code01: Code01,
-  pub code02: Code02,
-  pub code03: Code03,
-  pub code04: Code04,
-  pub code05: Code05,
+  code: [u8; 18]
 }
 
 impl UsciCode {
 
   pub fn calculate_code05(code01: &Code01, code02: &Code02, code03: &Code03, code04: &Code04) -> UsciResult<char> {
-    let mut result = 0;
-    result = result + UsciCode::calculate_char_weight(code01.get_code(), 0)?;
-    result = result + UsciCode::calculate_char_weight(code02.get_code(), 1)?;
-    let mut code3_chars = code03.code.chars();
-    for index in 2..=7 {
-      result = result + UsciCode::calculate_char_weight(code3_chars.next().unwrap(), index)?;
-    }
-    let mut code4_chars = code04.code.chars();
-    for index in 8..=16 {
-      result = result + UsciCode::calculate_char_weight(code4_chars.next().unwrap(), index)?;
-    }
-    result = result % 31;
-    result = if result == 0 { 0 } else { 31 - result };
-    Ok(CODE_CHARS.get(result).unwrap().clone())
+    let mut body = [0u8; 17];
+    body[0] = code01.get_code() as u8;
+    body[1] = code02.get_code() as u8;
+    body[2..8].copy_from_slice(&code03.code);
+    body[8..17].copy_from_slice(&code04.code);
+    return UsciCode::calculate_check(&body);
   }
 
-  fn calculate_char_weight(ch: char, index: usize) -> UsciResult<usize> {
-    match CODE_CHARS.binary_search(&ch) {
-      Ok(position) => {
-        Ok(CODE_WEIGHT.get(index).unwrap() * position)
-      }
-      Err(_) => {
-        Err(UsciError::CommonError(format!("Calculate Weight Error: {} {}", index, ch)))
-      }
-    }
+  fn calculate_check(body: &[u8; 17]) -> UsciResult<char> {
+    let result = checksum::GB_32100.compute_value(body)?;
+    Ok(CODE_CHARS[result])
+  }
+
+  // Code01 is implied by Code02 and Code05 is always computed here, so a code
+  // built from segments can never carry a stale check char.
+  fn from_segments(code02: Code02, code03: &Code03, code04: &Code04) -> Self {
+    let mut code = [0u8; 18];
+    code[0] = code02.get_code01().get_code() as u8;
+    code[1] = code02.get_code() as u8;
+    code[2..8].copy_from_slice(&code03.code);
+    code[8..17].copy_from_slice(&code04.code);
+    code[17] = UsciCode::calculate_check(code[..17].try_into().unwrap()).unwrap() as u8;
+    return UsciCode { code };
+  }
+
+  pub fn builder() -> UsciCodeBuilder {
+    return UsciCodeBuilder::default();
   }
 
-  pub fn from_random(rng: &mut ThreadRng) -> Self {
+  pub fn from_random<R: Rng + ?Sized>(rng: &mut R) -> Self {
     let code01 = Code01::from_random(rng);
     let code02 = Code02::from_random(&code01, rng);
     let code03 = Code03::from_random(rng);
     let code04 = Code04::from_random(rng);
-    let code05_ch = UsciCode::calculate_code05(&code01, &code02, &code03, &code04).unwrap();
-    return UsciCode {
-      code01,
-      code02,
-      code03,
-      code04,
-      code05: Code05 { code: code05_ch }
+    return UsciCode::from_segments(code02, &code03, &code04);
+  }
+
+  // Keeps every other segment, so it also applies to realistic codes.
+  pub fn into_test_code<R: Rng + ?Sized>(self, rng: &mut R) -> Self {
+    return self.with_region(Code03::from_random_test(rng));
+  }
+
+  pub fn is_test_code(&self) -> bool {
+    return self.region().is_test();
+  }
+
+  pub fn with_department(&self, code01: Code01) -> UsciResult<Self> {
+    let code02 = Code02::from_char(&code01, self.code[1] as char)?;
+    return Ok(UsciCode::from_segments(code02, &self.region(), &self.org_code()));
+  }
+
+  pub fn with_category(&self, code02: Code02) -> UsciResult<Self> {
+    if code02.get_code01() != self.code01() {
+      return Err(UsciError::CommonError(format!("Parse Code02 Error (Code01: {}): {}", self.code01(), code02)));
+    }
+    return Ok(UsciCode::from_segments(code02, &self.region(), &self.org_code()));
+  }
+
+  pub fn with_region(&self, code03: Code03) -> Self {
+    return UsciCode::from_segments(self.code02(), &code03, &self.org_code());
+  }
+
+  pub fn with_org_code(&self, code04: Code04) -> Self {
+    return UsciCode::from_segments(self.code02(), &self.region(), &code04);
+  }
+
+  pub fn region(&self) -> Code03 {
+    return Code03 { code: self.code[2..8].try_into().unwrap() };
+  }
+
+  pub fn org_code(&self) -> Code04 {
+    return Code04 { code: self.code[8..17].try_into().unwrap() };
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> UsciResult<Self> {
+    let code: [u8; 18] = match bytes.try_into() {
+      Ok(code) => code,
+      Err(_) => return Err(UsciError::CommonError(format!("Parse Code Error: {}", String::from_utf8_lossy(bytes))))
     };
+    let code01 = Code01::from_char(code[0] as char)?;
+    Code02::from_char(&code01, code[1] as char)?;
+    Code03::from_bytes(&code[2..8])?;
+    Code04::from_bytes(&code[8..17])?;
+    let code05_ch = UsciCode::calculate_check(code[..17].try_into().unwrap())?;
+    if code05_ch as u8 != code[17] {
+      return Err(UsciError::CommonError(format!("Verify Code05 Error: {}", code05_ch)));
+    }
+    return Ok(UsciCode { code });
+  }
+
+  // Unlike from_str, look-alike chars are replaced before parsing; they are
+  // returned so the caller can still tell the input was not genuine.
+  pub fn parse_normalized(code_str: &str) -> UsciResult<(Self, Vec<SpoofedChar>)> {
+    let (normalized, spoofed) = homoglyph::normalize(code_str);
+    match UsciCode::from_bytes(normalized.as_bytes()) {
+      Ok(code) => Ok((code, spoofed)),
+      Err(_) if !spoofed.is_empty() => Err(UsciError::SpoofedCharacter(spoofed)),
+      Err(err) => Err(err)
+    }
+  }
+
+  pub fn complete(body: &str) -> UsciResult<Self> {
+    let bytes = body.as_bytes();
+    if bytes.len() != 17 {
+      return Err(UsciError::CommonError(format!("Parse Code Error: {}", body)));
+    }
+    let code01 = Code01::from_char(bytes[0] as char)?;
+    let code02 = Code02::from_char(&code01, bytes[1] as char)?;
+    let code03 = Code03::from_bytes(&bytes[2..8])?;
+    let code04 = Code04::from_bytes(&bytes[8..17])?;
+    return Ok(UsciCode::from_segments(code02, &code03, &code04));
+  }
+
+  pub fn code01(&self) -> Code01 {
+    return Code01::from_char(self.code[0] as char).unwrap();
+  }
+
+  pub fn code02(&self) -> Code02 {
+    return Code02::from_char(&self.code01(), self.code[1] as char).unwrap();
+  }
+
+  pub fn code03(&self) -> &str {
+    return &self.as_str()[2..8];
+  }
+
+  pub fn code04(&self) -> &str {
+    return &self.as_str()[8..17];
+  }
+
+  pub fn code05(&self) -> char {
+    return self.code[17] as char;
   }
 
   pub fn get_code(&self) -> String {
-    let mut code_str = String::with_capacity(18);
-    code_str.push(self.code01.get_code());
-    code_str.push(self.code02.get_code());
-    code_str.push_str(self.code03.code.as_str());
-    code_str.push_str(self.code04.code.as_str());
-    code_str.push(self.code05.code);
-    return code_str;
+    return self.as_str().to_string();
+  }
+
+  pub fn as_str(&self) -> &str {
+    // SAFETY: every constructor, from_bytes, from_segments and from_packed,
+    // only stores chars of Code01, Code02, Code03 or CODE_CHARS, all ASCII,
+    // so the bytes are valid UTF-8 without checking them again.
+    return unsafe { std::str::from_utf8_unchecked(&self.code) };
+  }
+
+  pub fn as_bytes(&self) -> &[u8; 18] {
+    return &self.code;
+  }
+
+  // Mixed-radix number over (Code01, Code02) pair, six Code03 digits and nine
+  // Code04 chars; Code05 is dropped and recomputed when unpacking. The digit
+  // order follows the text, so packed values sort exactly like the codes.
+  pub fn to_packed(&self) -> u128 {
+    let code02 = self.code02();
+    let mut packed = Code02::ALL.iter().position(|item| *item == code02).unwrap() as u128;
+    for &byte in self.code[2..8].iter() {
+      packed = packed * 10 + (byte - b'0') as u128;
+    }
+    for &byte in self.code[8..17].iter() {
+      packed = packed * 31 + code_value(byte).unwrap() as u128;
+    }
+    return packed;
+  }
+
+  pub fn from_packed(packed: u128) -> UsciResult<Self> {
+    let mut rest = packed;
+    let mut code = [0u8; 18];
+    for byte in code[8..17].iter_mut().rev() {
+      *byte = CODE_CHARS[(rest % 31) as usize] as u8;
+      rest /= 31;
+    }
+    for byte in code[2..8].iter_mut().rev() {
+      *byte = b'0' + (rest % 10) as u8;
+      rest /= 10;
+    }
+    let code02 = match Code02::ALL.get(rest as usize) {
+      Some(code02) => code02,
+      None => return Err(UsciError::CommonError(format!("Unpack Code Error: {}", packed)))
+    };
+    code[0] = code02.get_code01().get_code() as u8;
+    code[1] = code02.get_code() as u8;
+    code[17] = UsciCode::calculate_check(code[..17].try_into().unwrap())? as u8;
+    return Ok(UsciCode { code });
+  }
+
+  pub fn to_packed_bytes(&self) -> [u8; PACKED_BYTES] {
+    let bytes = self.to_packed().to_be_bytes();
+    return bytes[16 - PACKED_BYTES..].try_into().unwrap();
+  }
+
+  pub fn from_packed_bytes(bytes: &[u8]) -> UsciResult<Self> {
+    if bytes.len() != PACKED_BYTES {
+      return Err(UsciError::CommonError(format!("Unpack Code Error: {:?}", bytes)));
+    }
+    let mut packed = [0u8; 16];
+    packed[16 - PACKED_BYTES..].copy_from_slice(bytes);
+    return UsciCode::from_packed(u128::from_be_bytes(packed));
   }
 }
 
@@ -624,32 +946,178 @@ impl FromStr for UsciCode {
   type Err = UsciError;
 
   fn from_str(code_str: &str) -> UsciResult<Self> {
-    if code_str.len() != 18 {
-      return Err(UsciError::CommonError(format!("Parse Code Error: {}", code_str)));
-    }
-    let mut code_chars = code_str.chars();
-    let code01 = Code01::from_char(code_chars.next().unwrap())?;
-    let code02 = Code02::from_char(&code01, code_chars.next().unwrap())?;
-    let mut code03_str = String::with_capacity(6);
-    for _ in 0..6 {
-      code03_str.push(code_chars.next().unwrap());
-    }
-    let code03 = Code03::new(code03_str.as_str())?;
-    let mut code04_str = String::with_capacity(9);
-    for _ in 0..9 {
-      code04_str.push(code_chars.next().unwrap());
+    if !code_str.is_ascii() {
+      let spoofed = homoglyph::find_spoofed(code_str);
+      if !spoofed.is_empty() {
+        return Err(UsciError::SpoofedCharacter(spoofed));
+      }
     }
-    let code04 = Code04::new(code04_str.as_str())?;
-    let code05_ch = UsciCode::calculate_code05(&code01, &code02, &code03, &code04)?;
-    if code05_ch != code_chars.next().unwrap() {
-      return Err(UsciError::CommonError(format!("Verify Code05 Error: {}", code05_ch)));
+    return UsciCode::from_bytes(code_str.as_bytes());
+  }
+}
+
+impl TryFrom<&str> for UsciCode {
+  type Error = UsciError;
+
+  fn try_from(code_str: &str) -> UsciResult<Self> {
+    UsciCode::from_str(code_str)
+  }
+}
+
+impl TryFrom<String> for UsciCode {
+  type Error = UsciError;
+
+  fn try_from(code_str: String) -> UsciResult<Self> {
+    UsciCode::from_str(code_str.as_str())
+  }
+}
+
+impl TryFrom<&[u8]> for UsciCode {
+  type Error = UsciError;
+
+  fn try_from(code_bytes: &[u8]) -> UsciResult<Self> {
+    UsciCode::from_bytes(code_bytes)
+  }
+}
+
+impl TryFrom<[u8; 18]> for UsciCode {
+  type Error = UsciError;
+
+  fn try_from(code_bytes: [u8; 18]) -> UsciResult<Self> {
+    UsciCode::from_bytes(&code_bytes)
+  }
+}
+
+impl Display for UsciCode {
+
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.pad(self.as_str())
+  }
+}
+
+impl AsRef<str> for UsciCode {
+
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+impl AsRef<[u8]> for UsciCode {
+
+  fn as_ref(&self) -> &[u8] {
+    &self.code
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UsciCodeBuilder {
+  code01: Option<Code01>,
+  code02: Option<Code02>,
+  code03: Option<Code03>,
+  code04: Option<Code04>
+}
+
+impl UsciCodeBuilder {
+
+  pub fn department(mut self, code01: Code01) -> Self {
+    self.code01 = Some(code01);
+    return self;
+  }
+
+  pub fn category(mut self, code02: Code02) -> Self {
+    self.code02 = Some(code02);
+    return self;
+  }
+
+  pub fn region(mut self, code03: Code03) -> Self {
+    self.code03 = Some(code03);
+    return self;
+  }
+
+  pub fn org_code(mut self, code04: Code04) -> Self {
+    self.code04 = Some(code04);
+    return self;
+  }
+
+  pub fn build(&self) -> UsciResult<UsciCode> {
+    let code02 = match self.code02 {
+      Some(code02) => code02,
+      None => return Err(UsciError::CommonError("Build Code Error: missing Code02".to_string()))
+    };
+    if let Some(code01) = self.code01 {
+      if code02.get_code01() != code01 {
+        return Err(UsciError::CommonError(format!("Parse Code02 Error (Code01: {}): {}", code01, code02)));
+      }
     }
-    return Ok(UsciCode {
-      code01,
-      code02,
-      code03,
-      code04,
-      code05: Code05 { code: code05_ch }
-    });
-  }
-}
\ No newline at end of file
+    let code03 = match self.code03 {
+      Some(code03) => code03,
+      None => return Err(UsciError::CommonError("Build Code Error: missing Code03".to_string()))
+    };
+    let code04 = match self.code04 {
+      Some(code04) => code04,
+      None => return Err(UsciError::CommonError("Build Code Error: missing Code04".to_string()))
+    };
+    return Ok(UsciCode::from_segments(code02, &code03, &code04));
+  }
+
+}
+
+// Hashing goes through the text so that `Borrow<str>` holds: a
+// `HashSet<UsciCode>` can be queried with a plain `&str`.
+impl Borrow<str> for UsciCode {
+
+  fn borrow(&self) -> &str {
+    self.as_str()
+  }
+}
+
+impl Hash for UsciCode {
+
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_str().hash(state)
+  }
+}
+
+// A code kept with its packed bytes, for database columns that store the
+// packed form instead of the text; still validated on every read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Binary))]
+pub struct PackedUsciCode {
+  code: UsciCode,
+  bytes: [u8; PACKED_BYTES]
+}
+
+impl PackedUsciCode {
+
+  pub fn new(code: UsciCode) -> Self {
+    return PackedUsciCode { code, bytes: code.to_packed_bytes() };
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> UsciResult<Self> {
+    return Ok(PackedUsciCode::new(UsciCode::from_packed_bytes(bytes)?));
+  }
+
+  pub fn code(&self) -> UsciCode {
+    return self.code;
+  }
+
+  pub fn as_bytes(&self) -> &[u8; PACKED_BYTES] {
+    return &self.bytes;
+  }
+
+}
+
+impl From<UsciCode> for PackedUsciCode {
+
+  fn from(code: UsciCode) -> Self {
+    return PackedUsciCode::new(code);
+  }
+}
+
+impl From<PackedUsciCode> for UsciCode {
+
+  fn from(packed: PackedUsciCode) -> Self {
+    return packed.code;
+  }
+}
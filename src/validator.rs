@@ -0,0 +1,31 @@
+use std::borrow::Cow;
+use validator::ValidationError;
+use crate::common::UsciError;
+use crate::invalid::Defect;
+use crate::value_parser::UsciCodeParser;
+
+// For `#[validate(custom(function = "usci::validator::validate_usci"))]`, or
+// `custom = "..."` before validator 0.18. The error has code "usci", the
+// library's message, the value, and either the defect found or the spoofed
+// chars.
+pub fn validate_usci(code: &str) -> Result<(), ValidationError> {
+  let error = match UsciCodeParser::new().parse(code) {
+    Ok(_) => return Ok(()),
+    Err(error) => error
+  };
+  let mut validation_error = ValidationError::new("usci");
+  validation_error.message = Some(Cow::Owned(error.to_string()));
+  validation_error.add_param(Cow::Borrowed("value"), &code);
+  match error {
+    UsciError::SpoofedCharacter(spoofed) => {
+      let spoofed: Vec<String> = spoofed.iter().map(|item| item.to_string()).collect();
+      validation_error.add_param(Cow::Borrowed("spoofed"), &spoofed);
+    }
+    _ => {
+      if let Some(defect) = Defect::diagnose(code) {
+        validation_error.add_param(Cow::Borrowed("defect"), &defect.get_code());
+      }
+    }
+  }
+  return Err(validation_error);
+}
@@ -0,0 +1,74 @@
+use std::ffi::OsStr;
+use std::str::FromStr;
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::{Arg, Command};
+use crate::common::{UsciError, UsciResult};
+use crate::usci::UsciCode;
+
+// Reads a UsciCode argument, so a bad code is refused while parsing the
+// command line with the message the library gives. Strict by default, like
+// FromStr; with strict off, look-alike chars are replaced as
+// `usci verify --normalize` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsciCodeParser {
+  strict: bool,
+  reject_test_codes: bool
+}
+
+impl UsciCodeParser {
+
+  pub fn new() -> Self {
+    return UsciCodeParser { strict: true, reject_test_codes: false };
+  }
+
+  pub fn strict(mut self, strict: bool) -> Self {
+    self.strict = strict;
+    return self;
+  }
+
+  pub fn reject_test_codes(mut self, reject_test_codes: bool) -> Self {
+    self.reject_test_codes = reject_test_codes;
+    return self;
+  }
+
+  pub fn parse(&self, code_str: &str) -> UsciResult<UsciCode> {
+    let code = if self.strict {
+      UsciCode::from_str(code_str)?
+    } else {
+      UsciCode::parse_normalized(code_str)?.0
+    };
+    if self.reject_test_codes && code.is_test_code() {
+      return Err(UsciError::CommonError(format!("Test Code Error: {}", code)));
+    }
+    return Ok(code);
+  }
+
+}
+
+impl Default for UsciCodeParser {
+
+  fn default() -> Self {
+    return UsciCodeParser::new();
+  }
+}
+
+impl TypedValueParser for UsciCodeParser {
+  type Value = UsciCode;
+
+  // Goes through clap's own parser for functions, so errors read like those
+  // of any other invalid value.
+  fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<UsciCode, clap::Error> {
+    let parser = *self;
+    let parse = move |code_str: &str| parser.parse(code_str);
+    return parse.parse_ref(cmd, arg, value);
+  }
+}
+
+// Lets `#[clap(value_parser)]` on a UsciCode field pick the strict parser.
+impl ValueParserFactory for UsciCode {
+  type Parser = UsciCodeParser;
+
+  fn value_parser() -> UsciCodeParser {
+    return UsciCodeParser::new();
+  }
+}
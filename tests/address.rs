@@ -0,0 +1,63 @@
+#![allow(clippy::needless_return)]
+
+use usci::address::{check_address, parse_address, Confidence, RegionMatch};
+use usci::UsciCode;
+
+fn code(region: &str) -> UsciCode {
+  return UsciCode::complete(&format!("91{}MA5GB2D51", region)).unwrap();
+}
+
+#[test]
+fn full_names_are_high_confidence() {
+  let region = parse_address("福建省福州市鼓楼区五四路1号");
+  assert_eq!(region.province, Some(("35", Confidence::High)));
+  assert_eq!(region.prefecture, Some(("3501", Confidence::High)));
+}
+
+#[test]
+fn short_names_are_medium_confidence() {
+  let region = parse_address("福建福州鼓楼区五四路1号");
+  assert_eq!(region.province, Some(("35", Confidence::Medium)));
+  assert_eq!(region.prefecture, Some(("3501", Confidence::Medium)));
+  assert_eq!(parse_address("广西南宁市").province, Some(("45", Confidence::Medium)));
+}
+
+#[test]
+fn prefecture_alone_implies_the_province() {
+  let region = parse_address("福州市鼓楼区");
+  assert_eq!(region.province, Some(("35", Confidence::High)));
+  assert_eq!(region.prefecture, Some(("3501", Confidence::High)));
+}
+
+#[test]
+fn short_prefecture_without_province_is_low_confidence() {
+  let region = parse_address("朝阳区建国路");
+  assert_eq!(region.prefecture.map(|(_, confidence)| confidence), Some(Confidence::Low));
+  assert_eq!(parse_address("北京市朝阳区建国路").prefecture, None);
+  assert_eq!(parse_address("北京市朝阳区建国路").province, Some(("11", Confidence::High)));
+}
+
+#[test]
+fn full_prefecture_name_beats_an_earlier_short_one() {
+  assert_eq!(parse_address("吉林省吉林市船营区").prefecture, Some(("2202", Confidence::High)));
+  assert_eq!(parse_address("吉林省长春市南关区").prefecture, Some(("2201", Confidence::High)));
+}
+
+#[test]
+fn unknown_addresses_name_nothing() {
+  let region = parse_address("五四路1号");
+  assert_eq!(region.province, None);
+  assert_eq!(region.prefecture, None);
+}
+
+#[test]
+fn compares_the_address_with_the_code() {
+  let same = check_address("福建省福州市鼓楼区", &code("350102"));
+  assert_eq!((same.result, same.confidence), (RegionMatch::Same, Confidence::High));
+  assert_eq!(check_address("广东省深圳市南山区", &code("350102")).result, RegionMatch::DifferentProvince);
+  let prefecture = check_address("福建省厦门市思明区", &code("350102"));
+  assert_eq!((prefecture.result, prefecture.confidence), (RegionMatch::DifferentPrefecture, Confidence::High));
+  assert_eq!(check_address("北京市朝阳区", &code("110105")).result, RegionMatch::Same);
+  assert_eq!(check_address("五四路1号", &code("350102")).result, RegionMatch::Unknown);
+  assert_eq!(check_address("福建省福州市", &code("990102")).result, RegionMatch::Unknown);
+}
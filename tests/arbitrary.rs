@@ -0,0 +1,39 @@
+#![cfg(feature = "arbitrary")]
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use arbitrary::{Arbitrary, Unstructured};
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
+use usci::invalid::{Defect, NearValid};
+use usci::space::CodeSpace;
+use usci::UsciCode;
+
+fn inputs() -> Vec<Vec<u8>> {
+  let mut rng = StdRng::seed_from_u64(47);
+  return (0..500).map(|_| {
+    let mut bytes = vec![0u8; 64];
+    rng.fill_bytes(&mut bytes);
+    bytes
+  }).collect();
+}
+
+#[test]
+fn generated_codes_validate() {
+  for bytes in inputs() {
+    let code = UsciCode::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    assert_eq!(UsciCode::from_str(code.as_str()).unwrap(), code);
+  }
+  // Exhausted input reads as zero, the first code of the space.
+  assert_eq!(UsciCode::arbitrary(&mut Unstructured::new(&[])).unwrap(), CodeSpace::new().unrank(0).unwrap());
+}
+
+#[test]
+fn near_valid_never_validates() {
+  for bytes in inputs() {
+    if let Ok(near_valid) = NearValid::arbitrary(&mut Unstructured::new(&bytes)) {
+      assert!(UsciCode::from_str(&near_valid.code).is_err(), "{}", near_valid.code);
+      assert!(Defect::diagnose(&near_valid.code).is_some());
+    }
+  }
+}
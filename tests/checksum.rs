@@ -0,0 +1,49 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use usci::checksum::{GB_11714, GB_32100, MOD_11_10, MOD_11_2, MOD_97_10};
+use usci::idcard::ResidentId;
+use usci::lei::Lei;
+use usci::UsciCode;
+
+// Worked examples from the standards, so a change that stays self-consistent
+// but drifts from the published algorithms still fails.
+
+#[test]
+fn gb_32100_known_answer() {
+  assert_eq!(GB_32100.compute("91350100M000100Y4").unwrap(), "3");
+  assert!(GB_32100.verify("91350100M000100Y43"));
+  assert!(!GB_32100.verify("91350100M000100Y44"));
+  assert!(UsciCode::from_str("91350100M000100Y43").is_ok());
+}
+
+#[test]
+fn gb_11714_known_answer() {
+  assert_eq!(GB_11714.compute("D2143569").unwrap(), "X");
+  assert!(GB_11714.verify("D2143569X"));
+  assert!(!GB_11714.verify("D21435690"));
+}
+
+#[test]
+fn mod_11_2_known_answer() {
+  assert_eq!(MOD_11_2.compute("11010519491231002").unwrap(), "X");
+  assert!(MOD_11_2.verify("11010519491231002X"));
+  assert!(ResidentId::from_str("11010519491231002X").is_ok());
+  assert!(ResidentId::from_str("110105194912310021").is_err());
+}
+
+#[test]
+fn mod_97_10_known_answer() {
+  assert_eq!(MOD_97_10.compute("794").unwrap(), "44");
+  assert!(MOD_97_10.verify("79444"));
+  assert_eq!(Lei::complete("5493001KJTIIGC8Y1R").unwrap().check(), "12");
+  assert!(Lei::from_str("5493001KJTIIGC8Y1R12").is_ok());
+  assert!(Lei::from_str("5493001KJTIIGC8Y1R13").is_err());
+}
+
+#[test]
+fn mod_11_10_known_answer() {
+  assert_eq!(MOD_11_10.compute("0794").unwrap(), "5");
+  assert!(MOD_11_10.verify("07945"));
+  assert!(!MOD_11_10.verify("07946"));
+}
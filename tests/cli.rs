@@ -0,0 +1,145 @@
+#![allow(clippy::needless_return)]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use usci::division;
+use usci::invalid::Defect;
+
+fn usci_with_input(args: &[&str], input: &str) -> String {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_usci"))
+    .args(args)
+    .env("NO_COLOR", "1")
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .spawn()
+    .unwrap();
+  child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+  let output = child.wait_with_output().unwrap();
+  return String::from_utf8(output.stdout).unwrap();
+}
+
+fn usci(args: &[&str]) -> String {
+  return usci_with_input(args, "");
+}
+
+fn usci_fails(args: &[&str]) -> bool {
+  return !Command::new(env!("CARGO_BIN_EXE_usci")).args(args).output().unwrap().status.success();
+}
+
+#[test]
+fn verify_rejects_look_alike_chars_by_default() {
+  let output = usci(&["verify", "9135０100M000100Y43", "91350100M000100Y43"]);
+  let lines: Vec<&str> = output.lines().collect();
+  assert_eq!(lines[0], "FALSE 9135０100M000100Y43");
+  assert!(lines[1].contains("SPOOFED 5: U+FF10"));
+  assert_eq!(lines[2], "TRUE  91350100M000100Y43");
+}
+
+#[test]
+fn verify_normalizes_look_alike_chars_on_request() {
+  let output = usci(&["verify", "--normalize", "9135０100MＯ00100Y43"]);
+  let lines: Vec<&str> = output.lines().collect();
+  assert_eq!(lines[0], "TRUE  9135０100MＯ00100Y43 -> 91350100M000100Y43");
+  assert!(lines[1].contains("SPOOFED 5: U+FF10"));
+  assert!(lines[2].contains("SPOOFED 10: U+FF2F"));
+  assert!(usci(&["verify", "--normalize", "9135０100M000100Y44"]).starts_with("FALSE"));
+}
+
+#[test]
+fn check_names_echoes_a_header_row() {
+  let input = "name,code\n张三加工厂,92350100M000100Y40\n\"福州某某有限公司,鼓楼\",91350100M000100Y43\n某某店,91350100M000100Y44\n";
+  let output = usci_with_input(&["check-names"], input);
+  let lines: Vec<&str> = output.lines().collect();
+  assert_eq!(lines[0], "name,code\tresult");
+  assert_eq!(lines[1], "张三加工厂,92350100M000100Y40\tTRUE");
+  assert_eq!(lines[2], "\"福州某某有限公司,鼓楼\",91350100M000100Y43\tUNKNOWN");
+  assert_eq!(lines[3], "某某店,91350100M000100Y44\tINVALID");
+}
+
+#[test]
+fn check_addresses_reads_rows_without_a_header() {
+  let input = "\"吉林省吉林市船营区\",91220200MA5GB2D51L\n福建省福州市鼓楼区,91350100M000100Y43\n";
+  let output = usci_with_input(&["check-addresses"], input);
+  let lines: Vec<&str> = output.lines().collect();
+  assert_eq!(lines.len(), 2);
+  assert_eq!(lines[0], "\"吉林省吉林市船营区\",91220200MA5GB2D51L\tTRUE high");
+  assert_eq!(lines[1], "福建省福州市鼓楼区,91350100M000100Y43\tTRUE high");
+}
+
+fn labels(output: &str) -> Vec<(String, String)> {
+  let mut lines = output.lines();
+  assert_eq!(lines.next(), Some("code,label"));
+  return lines.map(|line| {
+    let (code, label) = line.rsplit_once(',').unwrap();
+    (code.to_string(), label.to_string())
+  }).collect();
+}
+
+#[test]
+fn error_rate_breaks_that_share_of_codes() {
+  let rows = labels(&usci(&["generate", "--count", "4000", "--error-rate", "0.25"]));
+  assert_eq!(rows.len(), 4000);
+  let broken = rows.iter().filter(|(_, label)| label != "valid").count();
+  assert!((850..=1150).contains(&broken), "{} broken", broken);
+  for defect in ["length", "code01", "code02", "code03", "code04", "check", "transposed"] {
+    assert!(rows.iter().any(|(_, label)| label == defect), "no {}", defect);
+  }
+  for (code, label) in rows.iter() {
+    let diagnosed = Defect::diagnose(code).map_or("valid", |defect| defect.get_code());
+    let expected = if label == "transposed" { "check" } else { label.as_str() };
+    assert_eq!(diagnosed, expected, "{}", code);
+  }
+}
+
+#[test]
+fn error_rate_mixes_only_the_given_defects() {
+  let rows = labels(&usci(&["generate", "--count", "1000", "--error-rate", "1", "--invalid", "code03", "--invalid", "check"]));
+  assert!(rows.iter().all(|(_, label)| label == "code03" || label == "check"));
+  let code03 = rows.iter().filter(|(_, label)| label == "code03").count();
+  assert!((350..=650).contains(&code03), "{} code03", code03);
+  assert!(labels(&usci(&["generate", "--count", "100", "--error-rate", "0"])).iter().all(|(_, label)| label == "valid"));
+}
+
+#[test]
+fn records_under_a_category_have_known_regions() {
+  let output = usci(&["generate", "--count", "50", "--records", "csv", "--category", "93"]);
+  let mut lines = output.lines();
+  assert_eq!(lines.next(), Some("code,name,address,org_code,tax_id"));
+  for line in lines {
+    let fields: Vec<&str> = line.split(',').collect();
+    assert!(fields[0].starts_with("93"), "{}", line);
+    let province = division::province(&fields[0][2..8]).unwrap();
+    assert!(fields[2].starts_with(province), "{}", line);
+  }
+}
+
+#[test]
+fn records_reject_safe_codes_and_bad_tables() {
+  assert!(usci_fails(&["generate", "--records", "json", "--safe"]));
+  assert!(usci_fails(&["generate", "--records", "sql", "--table", "t; DROP TABLE t"]));
+  assert!(usci(&["generate", "--records", "sql", "--table", "crm.orgs"]).starts_with("INSERT INTO crm.orgs "));
+}
+
+#[test]
+fn weights_apply_to_records() {
+  let path = std::env::temp_dir().join(format!("usci-weights-{}.txt", std::process::id()));
+  std::fs::write(&path, "category 92 0\ncategory 93 0\n").unwrap();
+  let weights = path.to_str().unwrap();
+  let output = usci(&["generate", "--count", "20", "--records", "csv", "--weights", weights]);
+  let rows: Vec<&str> = output.lines().skip(1).collect();
+  assert_eq!(rows.len(), 20);
+  assert!(rows.iter().all(|row| !row.starts_with("92") && !row.starts_with("93")), "{}", output);
+  assert_eq!(usci(&["generate", "--count", "5", "--realistic", "--weights", weights]).lines().count(), 5);
+  assert!(usci_fails(&["generate", "--weights", weights]));
+  std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn solve_lists_or_counts_codes_derived_in_code03() {
+  let pattern = "913501??M000100Y43";
+  let listed = usci(&["solve", pattern]);
+  assert_eq!(listed.lines().count().to_string(), usci(&["solve", "--count", pattern]).trim());
+  assert!(listed.lines().any(|line| line == "91350100M000100Y43"));
+  let limited = usci(&["solve", "--limit", "1", pattern]);
+  assert!(limited.starts_with(&format!("{} candidates, more than the limit of 1", listed.lines().count())));
+}
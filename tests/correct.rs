@@ -0,0 +1,84 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use usci::correct::{ConfusionMatrix, Corrector};
+use usci::UsciCode;
+
+const CODE: &str = "91350100M000100Y43";
+
+// Sure of every char except the given ones.
+fn doubting(indices: &[usize]) -> Vec<f64> {
+  return (0..18).map(|index| if indices.contains(&index) { 0.5 } else { 0.99 }).collect();
+}
+
+#[test]
+fn recovers_a_single_substitution() {
+  let corrections = Corrector::default().correct("91350100MD00100Y43", Some(&doubting(&[9]))).unwrap();
+  assert_eq!(corrections[0].code, UsciCode::from_str(CODE).unwrap());
+  assert_eq!(corrections[0].edits, vec![(9, 'D', '0')]);
+}
+
+#[test]
+fn recovers_a_transposition() {
+  let corrections = Corrector::default().correct("91350100M001000Y43", Some(&doubting(&[11, 12]))).unwrap();
+  assert_eq!(corrections[0].code, UsciCode::from_str(CODE).unwrap());
+  assert_eq!(corrections[0].edits, vec![(11, '1', '0'), (12, '0', '1')]);
+}
+
+#[test]
+fn valid_code_ranks_itself_first() {
+  let corrections = Corrector::default().correct(CODE, None).unwrap();
+  assert_eq!(corrections[0].code, UsciCode::from_str(CODE).unwrap());
+  assert!(corrections[0].edits.is_empty());
+}
+
+#[test]
+fn max_edits_bounds_the_search() {
+  let observed = "9135D1DDM000100Y43";
+  let corrector = Corrector::default();
+  let found = |max_edits: usize| corrector.clone().max_edits(max_edits).correct(observed, None).unwrap();
+  let original = UsciCode::from_str(CODE).unwrap();
+  assert!(found(2).iter().all(|correction| correction.code != original));
+  assert!(found(3).iter().any(|correction| correction.code == original));
+  assert!(found(3).iter().all(|correction| correction.edits.len() <= 3));
+  assert!(Corrector::default().max_edits(0).correct("91350100M000100Y44", None).unwrap().is_empty());
+}
+
+#[test]
+fn corrections_are_ranked_by_score() {
+  let corrections = Corrector::default().correct("91350100M000100Y44", None).unwrap();
+  assert!(corrections.len() > 1);
+  assert!(corrections.windows(2).all(|pair| pair[0].score >= pair[1].score));
+  let mut codes: Vec<UsciCode> = corrections.iter().map(|correction| correction.code).collect();
+  codes.sort();
+  codes.dedup();
+  assert_eq!(codes.len(), corrections.len());
+}
+
+#[test]
+fn confidences_move_the_ranking() {
+  let observed = "91350100MD00100Y43";
+  let sure = Corrector::default().correct(observed, Some(&doubting(&[]))).unwrap();
+  let unsure = Corrector::default().correct(observed, Some(&doubting(&[9]))).unwrap();
+  let original = UsciCode::from_str(CODE).unwrap();
+  let score = |corrections: &[usci::correct::Correction]| corrections.iter().find(|correction| correction.code == original).unwrap().score;
+  assert!(score(&unsure) > score(&sure));
+}
+
+#[test]
+fn rejects_non_finite_or_negative_weights() {
+  for weight in ["NaN", "inf", "-inf", "-1"] {
+    assert!(ConfusionMatrix::from_reader(format!("8 B {}\n", weight).as_bytes()).is_err(), "{}", weight);
+  }
+  let matrix = ConfusionMatrix::from_reader("# OCR\n8 B 2.5\n\nB 8 0.5 # back\n".as_bytes()).unwrap();
+  assert_eq!(matrix.get('8', 'B'), 2.5);
+  assert_eq!(matrix.get('B', '8'), 0.5);
+}
+
+#[test]
+fn rejects_bad_confidences() {
+  let mut confidences = doubting(&[]);
+  confidences[3] = f64::NAN;
+  assert!(Corrector::default().correct(CODE, Some(&confidences)).is_err());
+  assert!(Corrector::default().correct(CODE, Some(&confidences[..17])).is_err());
+}
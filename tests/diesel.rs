@@ -0,0 +1,76 @@
+#![cfg(all(feature = "diesel", feature = "sqlite"))]
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use usci::{PackedUsciCode, UsciCode};
+
+diesel::table! {
+  organizations (rowid) {
+    rowid -> Integer,
+    code -> Text,
+    packed -> Binary,
+  }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = organizations)]
+struct NewOrganization {
+  code: UsciCode,
+  packed: PackedUsciCode
+}
+
+fn connect() -> SqliteConnection {
+  let mut conn = SqliteConnection::establish(":memory:").unwrap();
+  diesel::sql_query("CREATE TABLE organizations (code TEXT NOT NULL, packed BLOB NOT NULL)").execute(&mut conn).unwrap();
+  return conn;
+}
+
+#[test]
+fn round_trip() {
+  let mut conn = connect();
+  let codes: Vec<UsciCode> = (0..100).map(|_| UsciCode::from_random(&mut rand::thread_rng())).collect();
+  let rows: Vec<NewOrganization> = codes.iter().map(|code| NewOrganization { code: *code, packed: PackedUsciCode::new(*code) }).collect();
+  diesel::insert_into(organizations::table).values(&rows).execute(&mut conn).unwrap();
+  let loaded: Vec<(UsciCode, PackedUsciCode)> = organizations::table
+    .select((organizations::code, organizations::packed))
+    .order(organizations::rowid)
+    .load(&mut conn)
+    .unwrap();
+  assert_eq!(loaded.len(), codes.len());
+  for ((code, packed), expected) in loaded.iter().zip(codes.iter()) {
+    assert_eq!(code, expected);
+    assert_eq!(packed.code(), *expected);
+  }
+  let text: String = organizations::table.select(organizations::code).first(&mut conn).unwrap();
+  assert_eq!(text, codes[0].as_str());
+  let found: Vec<UsciCode> = organizations::table
+    .select(organizations::code)
+    .filter(organizations::code.eq(codes[1]))
+    .load(&mut conn)
+    .unwrap();
+  assert_eq!(found, vec![codes[1]]);
+}
+
+#[test]
+fn invalid_text_fails_to_load() {
+  let mut conn = connect();
+  diesel::sql_query("INSERT INTO organizations (code, packed) VALUES ('91350100M000100Y44', x'00')").execute(&mut conn).unwrap();
+  let result: QueryResult<UsciCode> = organizations::table.select(organizations::code).first(&mut conn);
+  assert!(result.is_err());
+  let text: String = organizations::table.select(organizations::code).first(&mut conn).unwrap();
+  assert!(UsciCode::from_str(&text).is_err());
+}
+
+#[test]
+fn invalid_packed_fails_to_load() {
+  let mut conn = connect();
+  diesel::sql_query("INSERT INTO organizations (code, packed) VALUES ('91350100M000100Y43', x'FFFFFFFFFFFFFFFFFF')").execute(&mut conn).unwrap();
+  diesel::sql_query("INSERT INTO organizations (code, packed) VALUES ('91350100M000100Y43', x'00')").execute(&mut conn).unwrap();
+  let results: Vec<QueryResult<PackedUsciCode>> = vec![
+    organizations::table.select(organizations::packed).order(organizations::rowid).first(&mut conn),
+    organizations::table.select(organizations::packed).order(organizations::rowid).offset(1).first(&mut conn)
+  ];
+  assert!(results.iter().all(|result| result.is_err()));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "fake")]
+#![allow(clippy::needless_return)]
+
+use fake::{Fake, Faker};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use usci::division;
+use usci::invalid::Defect;
+use usci::realistic::{RealisticGenerator, Weights};
+use usci::record::Record;
+use usci::space::CodeSpace;
+use usci::{Code04, UsciCode};
+
+#[test]
+fn seeded_fakes_repeat() {
+  let draw = |seed: u64| {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let codes: Vec<UsciCode> = (0..20).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+    let records: Vec<Record> = (0..5).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+    let org_code: Code04 = Faker.fake_with_rng(&mut rng);
+    let defect: Defect = Faker.fake_with_rng(&mut rng);
+    (codes, records, org_code, defect)
+  };
+  assert_eq!(draw(48), draw(48));
+  assert_ne!(draw(48).0, draw(49).0);
+}
+
+#[test]
+fn space_and_generator_configs_constrain_codes() {
+  let mut rng = StdRng::seed_from_u64(48);
+  let space = CodeSpace::new().category("92").unwrap().region("4403").unwrap();
+  for _ in 0..50 {
+    let code: UsciCode = space.fake_with_rng(&mut rng);
+    assert!(space.contains(&code));
+  }
+  let generator = RealisticGenerator::new(&Weights::default()).unwrap();
+  let record: Record = generator.fake_with_rng(&mut rng);
+  assert!(record.address.starts_with(division::province(record.code.code03()).unwrap()));
+}
+
+#[test]
+fn records_from_a_space_never_panic() {
+  let mut rng = StdRng::seed_from_u64(48);
+  let known = CodeSpace::new().category("93").unwrap().region("44").unwrap();
+  for _ in 0..50 {
+    let record: Record = known.fake_with_rng(&mut rng);
+    assert!(known.contains(&record.code));
+    assert!(record.address.starts_with("广东省"), "{}", record.address);
+  }
+  for space in [CodeSpace::new().region("99").unwrap(), CodeSpace::new().category("11").unwrap(), CodeSpace::new().region("000000").unwrap()] {
+    let record: Record = space.fake_with_rng(&mut rng);
+    assert!(space.contains(&record.code), "{}", record.code);
+  }
+}
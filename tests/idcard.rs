@@ -0,0 +1,65 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use usci::checksum::MOD_11_2;
+use usci::idcard::{mask, Gender, ResidentId};
+
+fn id(region: &str, birth_date: &str, sequence: &str) -> String {
+  return MOD_11_2.complete(&format!("{}{}{}", region, birth_date, sequence)).unwrap();
+}
+
+#[test]
+fn parses_the_segments() {
+  let resident_id = ResidentId::from_str("11010519491231002X").unwrap();
+  assert_eq!(resident_id.region(), "110105");
+  assert_eq!(resident_id.birth_date(), (1949, 12, 31));
+  assert_eq!(resident_id.sequence(), "002");
+  assert_eq!(resident_id.gender(), Gender::Female);
+  assert_eq!(resident_id.check(), 'X');
+  assert_eq!(ResidentId::from_str("11010519491231002x").unwrap(), resident_id);
+}
+
+#[test]
+fn validates_the_birth_date() {
+  assert!(ResidentId::from_str(&id("110105", "20000229", "001")).is_ok());
+  assert!(ResidentId::from_str(&id("110105", "19000228", "001")).is_ok());
+  for birth_date in ["19000229", "19990230", "19991301", "19990100", "19990001", "18991231"] {
+    assert!(ResidentId::from_str(&id("110105", birth_date, "001")).is_err(), "{}", birth_date);
+  }
+}
+
+#[test]
+fn rejects_birth_dates_in_the_future() {
+  assert!(ResidentId::from_str(&id("110105", "20991231", "001")).is_err());
+  assert!(ResidentId::from_str(&id("110105", "30000101", "001")).is_err());
+}
+
+#[test]
+fn requires_a_known_province_only() {
+  assert!(ResidentId::from_str(&id("110228", "19800101", "001")).is_ok());
+  assert!(ResidentId::from_str(&id("440399", "19800101", "001")).is_ok());
+  assert!(ResidentId::from_str(&id("990101", "19800101", "001")).is_err());
+  assert!(ResidentId::from_str(&id("000000", "19800101", "001")).is_err());
+}
+
+#[test]
+fn rejects_bad_shape_and_check() {
+  assert!(ResidentId::from_str("110105194912310021").is_err());
+  assert!(ResidentId::from_str("11010519491231002").is_err());
+  assert!(ResidentId::from_str("1101051949123100AX").is_err());
+}
+
+#[test]
+fn reads_legacy_numbers() {
+  let resident_id = ResidentId::from_legacy("110105491231002").unwrap();
+  assert_eq!(resident_id.as_str(), "11010519491231002X");
+  assert!(ResidentId::from_legacy("11010549123100").is_err());
+}
+
+#[test]
+fn masks_the_birth_date() {
+  assert_eq!(mask("11010519491231002X"), "110105********002X");
+  assert_eq!(ResidentId::from_str("11010519491231002X").unwrap().masked(), "110105********002X");
+  assert_eq!(mask("1101051949"), "1101051949");
+  assert_eq!(mask("１１０１０５19491231002X"), "１１０１０５19491231002X");
+}
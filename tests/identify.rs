@@ -0,0 +1,50 @@
+#![allow(clippy::needless_return)]
+
+use usci::checksum::MOD_11_10;
+use usci::identify::{identify, IdentifierKind};
+
+fn kinds(text: &str) -> Vec<(IdentifierKind, bool)> {
+  return identify(text).iter().map(|identification| (identification.kind, identification.valid)).collect();
+}
+
+#[test]
+fn identifies_usci_codes() {
+  assert_eq!(kinds("91350100M000100Y43"), vec![(IdentifierKind::Usci, true), (IdentifierKind::TaxpayerId, true)]);
+  assert_eq!(kinds(" 91350100m000100y43 ")[0], (IdentifierKind::Usci, true));
+  assert_eq!(kinds("91350100M000100Y44"), vec![(IdentifierKind::Usci, false), (IdentifierKind::TaxpayerId, false)]);
+}
+
+#[test]
+fn identifies_resident_ids() {
+  assert_eq!(kinds("11010519491231002X"), vec![(IdentifierKind::ResidentId, true), (IdentifierKind::TaxpayerId, true), (IdentifierKind::Usci, false)]);
+  assert!(kinds("110105491231002").contains(&(IdentifierKind::ResidentId, true)));
+}
+
+#[test]
+fn identifies_registration_numbers() {
+  let number = MOD_11_10.complete("44030010000001").unwrap();
+  assert_eq!(kinds(&number)[0], (IdentifierKind::RegistrationNumber, true));
+  assert!(kinds(&number).contains(&(IdentifierKind::ResidentId, false)));
+}
+
+#[test]
+fn identifies_taxpayer_ids() {
+  assert_eq!(kinds("350100D2143569X"), vec![(IdentifierKind::TaxpayerId, true)]);
+  assert_eq!(kinds("11010519491231002X01"), vec![(IdentifierKind::TaxpayerId, true)]);
+  assert_eq!(kinds("11010549123100201"), vec![(IdentifierKind::TaxpayerId, true)]);
+  assert_eq!(kinds("11010519491231002101"), vec![(IdentifierKind::TaxpayerId, false)]);
+}
+
+#[test]
+fn identifies_organization_codes() {
+  assert_eq!(kinds("D2143569X"), vec![(IdentifierKind::OrgCode, true)]);
+  assert_eq!(kinds("D2143569-X"), vec![(IdentifierKind::OrgCode, true)]);
+  assert_eq!(kinds("D21435690"), vec![(IdentifierKind::OrgCode, false)]);
+}
+
+#[test]
+fn ignores_other_text() {
+  assert!(identify("").is_empty());
+  assert!(identify("hello").is_empty());
+  assert!(identify("9135０100M000100Y43").is_empty());
+}
@@ -0,0 +1,68 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use usci::invalid::{Defect, NearValid};
+use usci::UsciCode;
+
+#[test]
+fn every_defect_is_diagnosed_as_itself() {
+  let mut rng = StdRng::seed_from_u64(45);
+  for defect in Defect::ALL {
+    for _ in 0..200 {
+      let original = UsciCode::from_random(&mut rng);
+      let corrupted = match defect.corrupt(&original, &mut rng) {
+        Some(corrupted) => corrupted,
+        None => continue
+      };
+      assert!(UsciCode::from_str(&corrupted).is_err(), "{} {}", defect, corrupted);
+      // A transposition leaves every char valid where it lands, so only the
+      // check char gives it away.
+      let expected = if defect == Defect::Transposed { Defect::Check } else { defect };
+      assert_eq!(Defect::diagnose(&corrupted), Some(expected), "{} {}", defect, corrupted);
+    }
+  }
+}
+
+#[test]
+fn transposition_swaps_neighbours_only() {
+  let mut rng = StdRng::seed_from_u64(45);
+  let original = UsciCode::from_str("91350100M000100Y43").unwrap();
+  for _ in 0..100 {
+    let corrupted = Defect::Transposed.corrupt(&original, &mut rng).unwrap();
+    let changed: Vec<usize> = corrupted.chars().zip(original.as_str().chars()).enumerate()
+      .filter(|(_, (left, right))| left != right)
+      .map(|(index, _)| index)
+      .collect();
+    assert_eq!(changed.len(), 2);
+    assert_eq!(changed[1], changed[0] + 1);
+  }
+  let uniform = UsciCode::complete("91000000000000000").unwrap();
+  assert_eq!(Defect::Transposed.corrupt(&uniform, &mut rng), None);
+}
+
+#[test]
+fn diagnoses_valid_codes_as_none() {
+  assert_eq!(Defect::diagnose("91350100M000100Y43"), None);
+  assert_eq!(Defect::diagnose("91350100M000100Y4"), Some(Defect::Length));
+  assert_eq!(Defect::diagnose("I1350100M000100Y43"), Some(Defect::Code01));
+}
+
+#[test]
+fn near_valid_keeps_the_original() {
+  let original = UsciCode::from_str("91350100M000100Y43").unwrap();
+  let near_valid = NearValid::new(original, Defect::Code04, 45).unwrap();
+  assert_eq!(near_valid.original, original);
+  assert_eq!(near_valid.defect, Defect::Code04);
+  assert_eq!(Defect::diagnose(&near_valid.code), Some(Defect::Code04));
+  assert_eq!(NearValid::new(original, Defect::Code04, 45), Some(near_valid));
+}
+
+#[test]
+fn defects_round_trip_through_their_names() {
+  for defect in Defect::ALL {
+    assert_eq!(Defect::from_str(&defect.to_string()).unwrap(), defect);
+  }
+  assert!(Defect::from_str("typo").is_err());
+}
@@ -0,0 +1,60 @@
+#![allow(clippy::needless_return)]
+
+use usci::legal_form::{category_label, check_name, legal_form, suffixes};
+use usci::{Code02, UsciCode};
+
+fn suffix(name: &str) -> Option<&'static str> {
+  return legal_form(name).map(|form| form.suffix);
+}
+
+fn code(category: &str) -> UsciCode {
+  return UsciCode::complete(&format!("{}350100M000100Y4", category)).unwrap();
+}
+
+#[test]
+fn longest_suffix_wins() {
+  assert_eq!(suffix("城关村股份经济合作社"), Some("股份经济合作社"));
+  assert_eq!(suffix("城关村经济合作社"), Some("经济合作社"));
+  assert_eq!(suffix("绿源农民专业合作社"), Some("农民专业合作社"));
+  assert_eq!(suffix("绿源合作社"), Some("合作社"));
+  assert_eq!(suffix("金杜律师事务所"), Some("律师事务所"));
+  assert_eq!(suffix("立信会计师事务所"), Some("事务所"));
+  assert_eq!(suffix("福州市总工会委员会"), Some("工会委员会"));
+  assert_eq!(suffix("某某"), None);
+}
+
+#[test]
+fn brackets_are_read_or_dropped() {
+  assert_eq!(suffix("福州创投投资中心（有限合伙）"), Some("有限合伙"));
+  assert_eq!(suffix("福州创投投资中心(普通合伙)"), Some("普通合伙"));
+  assert_eq!(suffix("福州某某有限公司（鼓楼分公司）"), Some("公司"));
+  assert_eq!(suffix(" 福州某某有限公司 "), Some("公司"));
+}
+
+#[test]
+fn factories_are_enterprises_or_individual_businesses() {
+  assert!(check_name("福州红星机械厂", &code("91")).is_consistent());
+  assert!(check_name("张三加工厂", &code("92")).is_consistent());
+  assert!(!check_name("张三加工厂", &code("93")).is_consistent());
+  assert_eq!(check_name("张三加工厂", &code("93")).expected(), &[Code02::L91, Code02::L92]);
+}
+
+#[test]
+fn checks_the_name_against_the_category() {
+  assert!(check_name("福州某某有限公司", &code("91")).is_consistent());
+  assert!(!check_name("福州某某有限公司", &code("92")).is_consistent());
+  assert!(check_name("绿源农民专业合作社", &code("93")).is_consistent());
+  assert!(check_name("福州市慈善基金会", &code("53")).is_consistent());
+  assert!(!check_name("福州市慈善基金会", &code("51")).is_consistent());
+  let unknown = check_name("某某", &code("91"));
+  assert!(unknown.legal_form.is_none() && unknown.is_consistent() && unknown.expected().is_empty());
+}
+
+#[test]
+fn lists_suffixes_and_labels_by_category() {
+  assert!(suffixes(&Code02::L92).contains(&"厂"));
+  assert!(suffixes(&Code02::L91).contains(&"公司"));
+  assert!(!suffixes(&Code02::L92).contains(&"公司"));
+  assert!(suffixes(&Code02::E21).is_empty());
+  assert_eq!(category_label(&Code02::L93), format!("93 {}", Code02::L93.get_type()));
+}
@@ -0,0 +1,66 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use usci::lei::{CrossReference, Lei};
+use usci::UsciCode;
+
+fn code(body: &str) -> UsciCode {
+  return UsciCode::complete(body).unwrap();
+}
+
+fn lei(body: &str) -> Lei {
+  return Lei::complete(body).unwrap();
+}
+
+#[test]
+fn parses_lei_segments() {
+  let lei = Lei::from_str("5493001KJTIIGC8Y1R12").unwrap();
+  assert_eq!(lei.lou(), "5493");
+  assert_eq!(lei.entity(), "001KJTIIGC8Y1R");
+  assert_eq!(lei.check(), "12");
+  assert!(Lei::from_str("5493001KJTIIGC8Y1R1").is_err());
+  assert!(Lei::from_str("5493001KJTIIGC8Y1R1A").is_err());
+}
+
+#[test]
+fn links_one_to_one() {
+  let mut xref = CrossReference::new();
+  let (usci, lei) = (code("91350100M000100Y4"), lei("5493001KJTIIGC8Y1R"));
+  xref.add(usci, lei);
+  xref.add(usci, lei);
+  assert_eq!(xref.leis(&usci), &[lei]);
+  assert_eq!(xref.codes(&lei), &[usci]);
+  assert!(!xref.is_mismatched(&usci, &lei));
+  assert!(xref.leis(&code("91440300MA5GB2D51")).is_empty());
+}
+
+#[test]
+fn flags_one_to_many_links() {
+  let mut xref = CrossReference::new();
+  let (first, second) = (code("91350100M000100Y4"), code("91440300MA5GB2D51"));
+  let (one, other) = (lei("5493001KJTIIGC8Y1R"), lei("5493001KJTIIGC8Y1Q"));
+  xref.add(first, one);
+  xref.add(first, other);
+  xref.add(second, one);
+  assert_eq!(xref.leis(&first), &[one, other]);
+  assert_eq!(xref.codes(&one), &[first, second]);
+  assert!(xref.is_mismatched(&first, &other));
+  assert!(xref.is_mismatched(&second, &one));
+  let mut clean = CrossReference::new();
+  clean.add(second, other);
+  assert!(!clean.is_mismatched(&second, &other));
+}
+
+#[test]
+fn skips_and_reports_bad_rows() {
+  let text = format!(
+    "# usci,lei\n{},{}\n91350100M000100Y44,{}\nnot a row\n\n{} , {} # trailing\n{},5493001KJTIIGC8Y1R13\n",
+    code("91350100M000100Y4"), lei("5493001KJTIIGC8Y1R"), lei("5493001KJTIIGC8Y1R"),
+    code("91440300MA5GB2D51"), lei("5493001KJTIIGC8Y1Q"), code("91440300MA5GB2D51")
+  );
+  let xref = CrossReference::from_reader(text.as_bytes()).unwrap();
+  assert_eq!(xref.rejected().iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![3, 4, 7]);
+  assert!(xref.rejected()[1].1.contains("not a row"));
+  assert_eq!(xref.leis(&code("91350100M000100Y4")).len(), 1);
+  assert_eq!(xref.leis(&code("91440300MA5GB2D51")).len(), 1);
+}
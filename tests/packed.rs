@@ -0,0 +1,54 @@
+#![allow(clippy::needless_return)]
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use usci::{Code02, PackedUsciCode, UsciCode};
+
+fn sample(count: usize) -> Vec<UsciCode> {
+  let mut rng = StdRng::seed_from_u64(28);
+  return (0..count).map(|_| UsciCode::from_random(&mut rng)).collect();
+}
+
+#[test]
+fn packing_round_trips() {
+  for code in sample(1000) {
+    assert_eq!(UsciCode::from_packed(code.to_packed()).unwrap(), code);
+    assert_eq!(UsciCode::from_packed_bytes(&code.to_packed_bytes()).unwrap(), code);
+    assert_eq!(PackedUsciCode::from_bytes(PackedUsciCode::new(code).as_bytes()).unwrap().code(), code);
+  }
+}
+
+#[test]
+fn packing_preserves_order() {
+  let mut codes = sample(1000);
+  codes.sort();
+  for pair in codes.windows(2) {
+    assert!(pair[0].to_packed() < pair[1].to_packed(), "{} {}", pair[0], pair[1]);
+    assert!(pair[0].to_packed_bytes() < pair[1].to_packed_bytes(), "{} {}", pair[0], pair[1]);
+    assert!(PackedUsciCode::new(pair[0]) < PackedUsciCode::new(pair[1]));
+  }
+}
+
+// Neighbours that differ only where the radix changes: across categories,
+// across a Code03 digit and across the digit/letter boundary of Code04.
+#[test]
+fn packing_preserves_order_at_boundaries() {
+  let mut codes: Vec<UsciCode> = Code02::ALL.iter()
+    .map(|code02| UsciCode::complete(&format!("{}440300MA5GB2D51", code02)).unwrap())
+    .collect();
+  for body in ["91440300MA5GB2D59", "91440300MA5GB2D5A", "91440399MA5GB2D51", "91440400000000000", "91440300YYYYYYYYY"] {
+    codes.push(UsciCode::complete(body).unwrap());
+  }
+  codes.sort();
+  for pair in codes.windows(2) {
+    assert!(pair[0].to_packed() < pair[1].to_packed(), "{} {}", pair[0], pair[1]);
+  }
+}
+
+#[test]
+fn packing_rejects_out_of_range() {
+  let max = Code02::ALL.len() as u128 * 1_000_000 * 31u128.pow(9);
+  assert!(UsciCode::from_packed(max).is_err());
+  assert!(UsciCode::from_packed(max - 1).is_ok());
+  assert!(UsciCode::from_packed_bytes(&[0; 8]).is_err());
+}
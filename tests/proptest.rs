@@ -0,0 +1,32 @@
+#![cfg(feature = "proptest")]
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use proptest::prelude::*;
+use usci::invalid::{Defect, NearValid};
+use usci::proptest::{near_valid, usci_code};
+use usci::space::CodeSpace;
+use usci::UsciCode;
+
+proptest! {
+  #[test]
+  fn generated_codes_validate(code in any::<UsciCode>()) {
+    prop_assert_eq!(UsciCode::from_str(code.as_str()).unwrap(), code);
+  }
+
+  #[test]
+  fn constrained_codes_stay_in_the_space(code in usci_code(CodeSpace::new().category("93").unwrap().region("4403").unwrap())) {
+    prop_assert!(code.as_str().starts_with("934403"));
+  }
+
+  #[test]
+  fn near_valid_never_validates(near_valid in any::<NearValid>()) {
+    prop_assert!(UsciCode::from_str(&near_valid.code).is_err());
+    prop_assert!(Defect::diagnose(&near_valid.code).is_some());
+  }
+
+  #[test]
+  fn near_valid_shows_its_defect(near_valid in near_valid(CodeSpace::new(), Defect::Code03)) {
+    prop_assert_eq!(Defect::diagnose(&near_valid.code), Some(Defect::Code03));
+  }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "quickcheck")]
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use quickcheck::{Arbitrary, QuickCheck};
+use usci::invalid::{Defect, NearValid};
+use usci::UsciCode;
+
+// Where the corruption starts; Code03 also changes the check char after it.
+fn position(near_valid: &NearValid) -> Option<usize> {
+  return near_valid.code.chars().zip(near_valid.original.as_str().chars()).position(|(left, right)| left != right);
+}
+
+#[test]
+fn generated_codes_validate() {
+  fn property(code: UsciCode) -> bool {
+    return UsciCode::from_str(code.as_str()).is_ok_and(|parsed| parsed == code);
+  }
+  QuickCheck::new().quickcheck(property as fn(UsciCode) -> bool);
+}
+
+#[test]
+fn near_valid_never_validates() {
+  fn property(near_valid: NearValid) -> bool {
+    return UsciCode::from_str(&near_valid.code).is_err() && Defect::diagnose(&near_valid.code).is_some();
+  }
+  QuickCheck::new().quickcheck(property as fn(NearValid) -> bool);
+}
+
+#[test]
+fn shrinking_keeps_the_defect_in_place() {
+  let original = UsciCode::from_str("91350100M000100Y43").unwrap();
+  for seed in 0..50 {
+    for defect in [Defect::Code03, Defect::Code04] {
+      let near_valid = NearValid::new(original, defect, seed).unwrap();
+      for shrunk in near_valid.shrink().take(20) {
+        assert_eq!((shrunk.defect, shrunk.seed), (defect, seed));
+        assert!(UsciCode::from_str(&shrunk.code).is_err());
+        assert_eq!(position(&shrunk), position(&near_valid), "{} {}", near_valid.code, shrunk.code);
+      }
+    }
+  }
+}
@@ -0,0 +1,46 @@
+#![allow(clippy::needless_return)]
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use usci::realistic::{RealisticGenerator, Weights};
+use usci::UsciCode;
+
+fn generate(weights: &str, count: usize) -> Vec<UsciCode> {
+  let weights = Weights::from_reader(weights.as_bytes()).unwrap();
+  let generator = RealisticGenerator::new(&weights).unwrap();
+  let mut rng = StdRng::seed_from_u64(41);
+  return (0..count).map(|_| generator.generate(&mut rng)).collect();
+}
+
+#[test]
+fn reads_a_weights_file() {
+  let codes = generate("department 9 1\ndepartment 5 0\ndepartment 1 0\ndepartment N 0\ndepartment 8 0\ndepartment 3 0\ncategory 92 0\ncategory 93 0\nregion 4403 1000000\nma 1\n", 200);
+  for code in codes.iter() {
+    assert_eq!(&code.as_str()[..2], "91", "{}", code);
+    assert!(code.code04().starts_with("MA"), "{}", code);
+  }
+  assert!(codes.iter().filter(|code| code.code03().starts_with("4403")).count() > 190);
+}
+
+#[test]
+fn skips_comments_and_blank_lines() {
+  let codes = generate("# only legacy organization codes\n\n   ma 0 # none in the MA series\n", 100);
+  assert!(codes.iter().all(|code| !code.code04().starts_with("MA")));
+}
+
+#[test]
+fn rejects_bad_weights() {
+  for line in ["department 9 -1", "department 9 NaN", "department 9 inf", "category 91 x", "ma 1.5", "ma", "department 9 1 2", "weight 9 1", "category 99 1", "department Z 1"] {
+    assert!(Weights::from_reader(line.as_bytes()).is_err(), "{}", line);
+  }
+}
+
+#[test]
+fn rejects_region_prefixes_of_the_wrong_length() {
+  assert!(Weights::from_reader("region 44 2".as_bytes()).is_ok());
+  assert!(Weights::from_reader("region 4403 2".as_bytes()).is_ok());
+  for line in ["region 4 2", "region 44030 2", "region 440300 2", "region 99 2", "region 4499 2"] {
+    let err = Weights::from_reader(line.as_bytes()).unwrap_err();
+    assert!(err.to_string().contains(line), "{} {}", line, err);
+  }
+}
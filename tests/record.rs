@@ -0,0 +1,87 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use usci::division;
+use usci::realistic::{RealisticGenerator, Weights};
+use usci::record::{is_sql_identifier, parse_csv_line, Record};
+use usci::space::CodeSpace;
+use usci::UsciCode;
+
+fn record(name: &str, address: &str) -> Record {
+  let mut record = Record::from_code(UsciCode::from_str("91350100M000100Y43").unwrap(), &mut StdRng::seed_from_u64(46));
+  record.name = name.to_string();
+  record.address = address.to_string();
+  return record;
+}
+
+#[test]
+fn fields_agree_with_the_code() {
+  let record = record("福州华兴科技有限公司", "福建省福州市人民路1号");
+  assert_eq!(record.org_code, "M000100Y-4");
+  assert_eq!(record.tax_id.as_str(), "91350100M000100Y43");
+  let generated = Record::from_code(UsciCode::from_str("91350100M000100Y43").unwrap(), &mut StdRng::seed_from_u64(46));
+  assert!(generated.address.starts_with("福建省福州市"));
+}
+
+#[test]
+fn escapes_json() {
+  let json = record("A \"B\" \\ C", "line\nbreak\t").to_json();
+  assert!(json.contains(r#""name": "A \"B\" \\ C""#), "{}", json);
+  assert!(json.contains(r#""address": "line\u000abreak\u0009""#), "{}", json);
+  assert!(json.starts_with(r#"{"code": "91350100M000100Y43""#));
+}
+
+#[test]
+fn escapes_csv() {
+  let csv = record("福州,\"华兴\"", "plain").to_csv();
+  assert_eq!(csv, "91350100M000100Y43,\"福州,\"\"华兴\"\"\",plain,M000100Y-4,91350100M000100Y43");
+  assert_eq!(parse_csv_line(&csv)[1], "福州,\"华兴\"");
+}
+
+#[test]
+fn escapes_sql() {
+  let sql = record("O'Brien's", "plain").to_sql("crm.organizations").unwrap();
+  assert_eq!(sql, "INSERT INTO crm.organizations (code, name, address, org_code, tax_id) VALUES ('91350100M000100Y43', 'O''Brien''s', 'plain', 'M000100Y-4', '91350100M000100Y43');");
+  assert!(record("a", "b").to_sql("x; DROP TABLE y").is_err());
+}
+
+#[test]
+fn accepts_plain_table_names_only() {
+  for table in ["organizations", "_tmp", "crm.organizations", "t1"] {
+    assert!(is_sql_identifier(table), "{}", table);
+  }
+  for table in ["", "1t", "crm.", ".t", "a b", "a-b", "t;", "\"t\"", "表"] {
+    assert!(!is_sql_identifier(table), "{}", table);
+  }
+}
+
+#[test]
+fn parses_quoted_csv_fields() {
+  assert_eq!(parse_csv_line("a,b"), vec!["a", "b"]);
+  assert_eq!(parse_csv_line("\"a,b\",c"), vec!["a,b", "c"]);
+  assert_eq!(parse_csv_line("\"say \"\"hi\"\"\",c"), vec!["say \"hi\"", "c"]);
+  assert_eq!(parse_csv_line(""), vec![""]);
+}
+
+#[test]
+fn addresses_stay_in_known_regions() {
+  let mut rng = StdRng::seed_from_u64(46);
+  let spaces = [
+    CodeSpace::new(),
+    CodeSpace::new().category("93").unwrap(),
+    CodeSpace::new().region("44").unwrap(),
+    CodeSpace::new().category("92").unwrap().region("4403").unwrap()
+  ];
+  for space in spaces.iter() {
+    let generator = RealisticGenerator::within(&Weights::default(), space).unwrap();
+    for _ in 0..200 {
+      let record = Record::from_code(generator.generate(&mut rng), &mut rng);
+      assert!(space.contains(&record.code), "{}", record.code);
+      let province = division::province(record.code.code03()).unwrap();
+      assert!(record.address.starts_with(province), "{} {}", record.code, record.address);
+    }
+  }
+  assert!(RealisticGenerator::within(&Weights::default(), &CodeSpace::new().region("99").unwrap()).is_err());
+}
@@ -0,0 +1,83 @@
+#![allow(clippy::needless_return)]
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use usci::solve::Solver;
+use usci::{Code02, UsciCode};
+
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Every completion of the pattern that parses, by trying each alphanumeric
+// at each wildcard; only for patterns with a wildcard or two.
+fn brute_force(pattern: &str) -> BTreeSet<UsciCode> {
+  let mut codes = vec![pattern.as_bytes().to_vec()];
+  for index in 0..pattern.len() {
+    if pattern.as_bytes()[index] == b'?' {
+      codes = codes.iter()
+        .flat_map(|code| ALPHANUMERIC.iter().map(move |&byte| {
+          let mut code = code.clone();
+          code[index] = byte;
+          code
+        }))
+        .collect();
+    }
+  }
+  return codes.iter().filter_map(|code| UsciCode::try_from(code.as_slice()).ok()).collect();
+}
+
+fn solutions(solver: &Solver) -> BTreeSet<UsciCode> {
+  let codes: Vec<UsciCode> = solver.iter().collect();
+  let unique: BTreeSet<UsciCode> = codes.iter().copied().collect();
+  assert_eq!(codes.len(), unique.len());
+  assert_eq!(solver.count(), codes.len() as u128);
+  return unique;
+}
+
+#[test]
+fn complete_pattern_has_one_solution() {
+  let solver = Solver::new("91350100M000100Y43").unwrap();
+  assert_eq!(solver.count(), 1);
+  assert_eq!(solver.iter().collect::<Vec<_>>(), vec![UsciCode::from_str("91350100M000100Y43").unwrap()]);
+  assert_eq!(Solver::new("91350100M000100Y44").unwrap().count(), 0);
+}
+
+#[test]
+fn known_check_fixes_one_wildcard() {
+  for pattern in ["91350100M00?100Y43", "91350100M000100Y?3", "9135010?M000100Y43", "9?350100M000100Y43"] {
+    let solver = Solver::new(pattern).unwrap();
+    assert_eq!(solutions(&solver), brute_force(pattern), "{}", pattern);
+  }
+  assert_eq!(Solver::new("91350100M00?100Y43").unwrap().count(), 1);
+}
+
+#[test]
+fn free_check_counts_every_completion() {
+  assert_eq!(Solver::new("91350100M000100Y?").unwrap().count(), 31);
+  assert_eq!(Solver::new("91350100M000100??").unwrap().count(), 31 * 31);
+  assert_eq!(Solver::new("91350100M000100??3").unwrap().count(), 31);
+  for pattern in ["91350100M000100Y??", "91350100M0001?0Y?3", "9135010?M000100Y4?"] {
+    let solver = Solver::new(pattern).unwrap();
+    assert_eq!(solutions(&solver), brute_force(pattern), "{}", pattern);
+  }
+}
+
+#[test]
+fn category_and_region_restrict_candidates() {
+  let pattern = "??350100M000100Y4?";
+  assert_eq!(Solver::new(pattern).unwrap().count(), Code02::ALL.len() as u128);
+  let under_9 = Code02::ALL.iter().filter(|code02| code02.get_code01().get_code() == '9').count();
+  assert_eq!(Solver::new(pattern).unwrap().category("9").unwrap().count(), under_9 as u128);
+  assert_eq!(Solver::new(pattern).unwrap().category("91").unwrap().count(), 1);
+  assert!(Solver::new("92350100M000100Y4?").unwrap().category("91").is_err());
+  let solver = Solver::new("913501??M000100Y43").unwrap();
+  assert_eq!(solutions(&solver), brute_force("913501??M000100Y43"));
+  assert_eq!(solver.clone().region("350100").unwrap().count(), 1);
+  assert!(solver.region("4403").is_err());
+}
+
+#[test]
+fn known_count_only_when_no_enumeration_is_needed() {
+  assert_eq!(Solver::new("91350100M000100Y?").unwrap().known_count(), Some(31));
+  assert_eq!(Solver::new("91350100M00?100Y43").unwrap().known_count(), Some(1));
+  assert_eq!(Solver::new("913501??M000100Y43").unwrap().known_count(), None);
+}
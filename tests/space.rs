@@ -0,0 +1,76 @@
+#![allow(clippy::needless_return)]
+
+use std::collections::HashSet;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use usci::space::{CodeSpace, Permutation};
+
+#[test]
+fn permutation_is_a_bijection() {
+  for size in [1u128, 2, 3, 31, 100, 1000, 4097] {
+    for key in [0u64, 1, 44, u64::MAX] {
+      let permutation = Permutation::new(key, size);
+      let images: HashSet<u128> = (0..size).map(|index| permutation.permute(index)).collect();
+      assert_eq!(images.len() as u128, size, "key {} size {}", key, size);
+      assert!(images.iter().all(|&image| image < size));
+      for index in 0..size {
+        assert_eq!(permutation.invert(permutation.permute(index)), index);
+      }
+    }
+  }
+}
+
+#[test]
+fn permutation_inverts_on_large_spaces() {
+  let space = CodeSpace::new().category("91").unwrap().region("4403").unwrap();
+  let permutation = Permutation::new(44, space.size());
+  let mut rng = StdRng::seed_from_u64(44);
+  for _ in 0..1000 {
+    let index = rng.gen_range(0..space.size());
+    let image = permutation.permute(index);
+    assert!(image < space.size());
+    assert_eq!(permutation.invert(image), index);
+  }
+}
+
+#[test]
+fn rank_and_unrank_round_trip() {
+  let spaces = [
+    CodeSpace::new(),
+    CodeSpace::new().category("9").unwrap(),
+    CodeSpace::new().category("93").unwrap().region("44").unwrap(),
+    CodeSpace::new().region("440305").unwrap()
+  ];
+  let mut rng = StdRng::seed_from_u64(44);
+  for space in spaces.iter() {
+    for index in [0, 1, CodeSpace::org_codes() - 1, CodeSpace::org_codes(), space.size() - 1] {
+      let code = space.unrank(index).unwrap();
+      assert!(space.contains(&code));
+      assert_eq!(space.rank(&code), Some(index));
+    }
+    for _ in 0..1000 {
+      let code = space.sample(&mut rng);
+      assert_eq!(space.unrank(space.rank(&code).unwrap()).unwrap(), code);
+    }
+    assert!(space.unrank(space.size()).is_err());
+  }
+}
+
+#[test]
+fn rank_follows_text_order() {
+  let space = CodeSpace::new().category("9").unwrap().region("44").unwrap();
+  let mut rng = StdRng::seed_from_u64(44);
+  let mut codes: Vec<_> = (0..1000).map(|_| space.sample(&mut rng)).collect();
+  codes.sort();
+  codes.dedup();
+  for pair in codes.windows(2) {
+    assert!(space.rank(&pair[0]) < space.rank(&pair[1]), "{} {}", pair[0], pair[1]);
+  }
+}
+
+#[test]
+fn rank_rejects_codes_outside_the_space() {
+  let space = CodeSpace::new().category("91").unwrap().region("4403").unwrap();
+  assert_eq!(space.rank(&CodeSpace::new().region("110101").unwrap().unrank(0).unwrap()), None);
+  assert_eq!(space.rank(&CodeSpace::new().category("92").unwrap().region("4403").unwrap().unrank(0).unwrap()), None);
+}
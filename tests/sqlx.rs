@@ -0,0 +1,66 @@
+#![cfg(all(feature = "sqlx", feature = "sqlite"))]
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use futures_executor::block_on;
+use sqlx::{Connection, SqliteConnection};
+use usci::{PackedUsciCode, UsciCode};
+
+fn connect() -> SqliteConnection {
+  return block_on(async {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE organizations (code TEXT NOT NULL, packed BLOB NOT NULL)").execute(&mut conn).await.unwrap();
+    conn
+  });
+}
+
+#[test]
+fn round_trip() {
+  let mut conn = connect();
+  let codes: Vec<UsciCode> = (0..100).map(|_| UsciCode::from_random(&mut rand::thread_rng())).collect();
+  block_on(async {
+    for code in codes.iter() {
+      sqlx::query("INSERT INTO organizations (code, packed) VALUES (?, ?)")
+        .bind(code)
+        .bind(PackedUsciCode::new(*code))
+        .execute(&mut conn).await.unwrap();
+    }
+    let rows: Vec<(UsciCode, PackedUsciCode)> = sqlx::query_as("SELECT code, packed FROM organizations").fetch_all(&mut conn).await.unwrap();
+    assert_eq!(rows.len(), codes.len());
+    for ((code, packed), expected) in rows.iter().zip(codes.iter()) {
+      assert_eq!(code, expected);
+      assert_eq!(packed.code(), *expected);
+    }
+    let text: String = sqlx::query_scalar("SELECT code FROM organizations LIMIT 1").fetch_one(&mut conn).await.unwrap();
+    assert_eq!(text, codes[0].as_str());
+  });
+}
+
+#[test]
+fn invalid_text_fails_to_decode() {
+  let mut conn = connect();
+  block_on(async {
+    let code = UsciCode::from_str("91350100M000100Y43").unwrap();
+    sqlx::query("INSERT INTO organizations (code, packed) VALUES ('91350100M000100Y44', ?)")
+      .bind(PackedUsciCode::new(code))
+      .execute(&mut conn).await.unwrap();
+    let result: Result<UsciCode, _> = sqlx::query_scalar("SELECT code FROM organizations").fetch_one(&mut conn).await;
+    assert!(result.is_err());
+    let packed: PackedUsciCode = sqlx::query_scalar("SELECT packed FROM organizations").fetch_one(&mut conn).await.unwrap();
+    assert_eq!(packed.code(), code);
+  });
+}
+
+#[test]
+fn invalid_packed_fails_to_decode() {
+  let mut conn = connect();
+  block_on(async {
+    sqlx::query("INSERT INTO organizations (code, packed) VALUES ('91350100M000100Y43', x'FFFFFFFFFFFFFFFFFF')").execute(&mut conn).await.unwrap();
+    sqlx::query("INSERT INTO organizations (code, packed) VALUES ('91350100M000100Y43', x'00')").execute(&mut conn).await.unwrap();
+    let results: Vec<Result<PackedUsciCode, _>> = vec![
+      sqlx::query_scalar("SELECT packed FROM organizations LIMIT 1").fetch_one(&mut conn).await,
+      sqlx::query_scalar("SELECT packed FROM organizations LIMIT 1 OFFSET 1").fetch_one(&mut conn).await
+    ];
+    assert!(results.iter().all(|result| result.is_err()));
+  });
+}
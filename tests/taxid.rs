@@ -0,0 +1,64 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use usci::taxid::{mask_rejected, TaxpayerForm, TaxpayerId};
+use usci::UsciError;
+
+fn form(code: &str) -> TaxpayerForm {
+  return TaxpayerId::from_str(code).unwrap().form();
+}
+
+#[test]
+fn reads_every_form() {
+  assert_eq!(form("91350100M000100Y43"), TaxpayerForm::Usci);
+  assert_eq!(form("350100D2143569X"), TaxpayerForm::Legacy);
+  assert_eq!(form("11010519491231002X"), TaxpayerForm::ResidentId);
+  assert_eq!(form("11010549123100201"), TaxpayerForm::LegacyResidentIdWithSuffix);
+  assert_eq!(form("11010519491231002X01"), TaxpayerForm::ResidentIdWithSuffix);
+  assert_eq!(form(" 11010519491231002x01 "), TaxpayerForm::ResidentIdWithSuffix);
+}
+
+#[test]
+fn exposes_the_underlying_code() {
+  assert_eq!(TaxpayerId::from_str("91350100M000100Y43").unwrap().usci().unwrap().to_string(), "91350100M000100Y43");
+  assert!(TaxpayerId::from_str("350100D2143569X").unwrap().usci().is_none());
+  for code in ["11010519491231002X", "11010549123100201", "11010519491231002X01"] {
+    assert_eq!(TaxpayerId::from_str(code).unwrap().resident_id().unwrap().as_str(), "11010519491231002X", "{}", code);
+  }
+}
+
+#[test]
+fn accepts_legacy_ids_under_old_prefecture_codes() {
+  assert_eq!(form("110228D2143569X"), TaxpayerForm::Legacy);
+  assert!(TaxpayerId::from_str("990100D2143569X").is_err());
+  assert!(TaxpayerId::from_str("350100D21435690").is_err());
+}
+
+#[test]
+fn rejects_bad_suffixes_and_ids() {
+  assert!(TaxpayerId::from_str("11010519491231002XA1").is_err());
+  assert!(TaxpayerId::from_str("1101051949123100AB").is_err());
+  assert!(TaxpayerId::from_str("11010519491231002101").is_err());
+  assert!(TaxpayerId::from_str("1101054912310020A").is_err());
+  assert!(TaxpayerId::from_str("3501").is_err());
+  assert!(matches!(TaxpayerId::from_str("9135０100M000100Y43"), Err(UsciError::SpoofedCharacter(_))));
+}
+
+#[test]
+fn masks_personal_forms_only() {
+  assert_eq!(TaxpayerId::from_str("11010519491231002X").unwrap().masked(), "110105********002X");
+  assert_eq!(TaxpayerId::from_str("11010519491231002X01").unwrap().masked(), "110105********002X01");
+  assert_eq!(TaxpayerId::from_str("11010549123100201").unwrap().masked(), "110105********201");
+  assert_eq!(TaxpayerId::from_str("91350100M000100Y43").unwrap().masked(), "91350100M000100Y43");
+  assert_eq!(TaxpayerId::from_str("350100D2143569X").unwrap().masked(), "350100D2143569X");
+}
+
+#[test]
+fn masks_rejected_input_shaped_like_an_id() {
+  assert_eq!(mask_rejected("110105199912310021"), "110105********0021");
+  assert_eq!(mask_rejected("11010519991231002101"), "110105********002101");
+  assert_eq!(mask_rejected("11010599123100201"), "110105********201");
+  assert_eq!(mask_rejected("350100M00010044"), "350100M00010044");
+  assert_eq!(mask_rejected("91350100M000100Y44"), "91350100M000100Y44");
+  assert_eq!(mask_rejected("1101051999"), "1101051999");
+}
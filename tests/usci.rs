@@ -0,0 +1,49 @@
+#![allow(clippy::needless_return)]
+
+use std::str::FromStr;
+use usci::{Code01, Code02, Code03, Code04, Code05, UsciCode};
+
+#[test]
+fn farmer_cooperative_is_93() {
+  assert_eq!(Code02::L93.get_code(), '3');
+  assert_eq!(Code02::from_char(&Code01::C09, '3').unwrap(), Code02::L93);
+  assert!(Code02::from_char(&Code01::C09, '9').is_err());
+  assert_eq!(UsciCode::complete("93440300MA5GB2D51").unwrap().code02(), Code02::L93);
+}
+
+#[test]
+fn diplomatic_other_is_29() {
+  assert_eq!(Code02::E29.get_code(), '9');
+  assert_eq!(Code02::from_char(&Code01::C02, '9').unwrap(), Code02::E29);
+  assert!(Code02::from_char(&Code01::C02, '2').is_err());
+  assert_eq!(UsciCode::complete("29440300MA5GB2D51").unwrap().code02(), Code02::E29);
+}
+
+#[test]
+fn every_category_round_trips() {
+  for code02 in Code02::ALL.iter() {
+    assert_eq!(Code02::from_char(&code02.get_code01(), code02.get_code()).unwrap(), *code02);
+    assert_eq!(Code02::from_str(&code02.to_string()).unwrap(), *code02);
+  }
+}
+
+#[test]
+fn every_segment_round_trips_through_display() {
+  for code01 in Code01::ALL.iter() {
+    assert_eq!(Code01::from_str(&code01.to_string()).unwrap(), *code01);
+  }
+  for code03 in ["000000", "110105", "440305", "999999"] {
+    assert_eq!(Code03::from_str(code03).unwrap().to_string(), code03);
+  }
+  for code04 in ["000000000", "MA5GB2D51", "YYYYYYYYY"] {
+    assert_eq!(Code04::from_str(code04).unwrap().to_string(), code04);
+  }
+  for ch in "0123456789ABCDEFGHJKLMNPQRTUWXY".chars() {
+    let code05 = Code05::from_str(&ch.to_string()).unwrap();
+    assert_eq!(Code05::from_str(&code05.to_string()).unwrap(), code05);
+  }
+  let code = UsciCode::from_str("91350100M000100Y43").unwrap();
+  assert_eq!(Code02::from_str(&code.code02().to_string()).unwrap(), code.code02());
+  assert_eq!(code.to_string().parse::<UsciCode>().unwrap(), code);
+  assert_eq!(format!("{:>4}", Code02::L93), "  93");
+}
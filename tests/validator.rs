@@ -0,0 +1,38 @@
+#![cfg(feature = "validator")]
+#![allow(clippy::needless_return)]
+
+use usci::validator::validate_usci;
+use validator::Validate;
+
+#[derive(Validate)]
+struct Organization {
+  #[validate(custom(function = "validate_usci"))]
+  code: String
+}
+
+fn error(code: &str) -> Option<validator::ValidationError> {
+  let organization = Organization { code: code.to_string() };
+  return organization.validate().err().map(|errors| errors.field_errors().get("code").unwrap()[0].clone());
+}
+
+#[test]
+fn accepts_valid_codes() {
+  assert!(error("91350100M000100Y43").is_none());
+}
+
+#[test]
+fn rejects_a_bad_check_char() {
+  let error = error("91350100M000100Y44").unwrap();
+  assert_eq!(error.code, "usci");
+  assert_eq!(error.params["defect"], "check");
+  assert_eq!(error.params["value"], "91350100M000100Y44");
+  assert!(error.message.unwrap().contains("Verify Code05 Error"));
+}
+
+#[test]
+fn reports_the_defect_or_spoofed_chars() {
+  assert_eq!(error("9135010").unwrap().params["defect"], "length");
+  let spoofed = error("9135０100M000100Y43").unwrap();
+  assert!(!spoofed.params.contains_key("defect"));
+  assert_eq!(spoofed.params["spoofed"][0], "5: U+FF10 '０' looks like '0'");
+}
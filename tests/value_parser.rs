@@ -0,0 +1,55 @@
+#![allow(clippy::needless_return)]
+
+use std::io::Write;
+use std::process::{Command as Process, Stdio};
+use std::str::FromStr;
+use clap::{Arg, Command};
+use usci::value_parser::UsciCodeParser;
+use usci::UsciCode;
+
+fn command(parser: UsciCodeParser) -> Command<'static> {
+  return Command::new("import").arg(Arg::new("code").required(true).value_parser(parser));
+}
+
+fn parse(parser: UsciCodeParser, code: &str) -> Result<UsciCode, String> {
+  return command(parser).try_get_matches_from(["import", code])
+    .map(|matches| *matches.get_one::<UsciCode>("code").unwrap())
+    .map_err(|err| err.to_string());
+}
+
+// What `usci pack` prints for a code it cannot read.
+fn cli_error(code: &str) -> String {
+  let mut child = Process::new(env!("CARGO_BIN_EXE_usci")).arg("pack").stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().unwrap();
+  writeln!(child.stdin.take().unwrap(), "{}", code).unwrap();
+  return String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap();
+}
+
+#[test]
+fn accepts_valid_codes() {
+  assert_eq!(parse(UsciCodeParser::new(), "91350100M000100Y43").unwrap(), UsciCode::from_str("91350100M000100Y43").unwrap());
+}
+
+#[test]
+fn rejects_bad_codes_with_the_cli_message() {
+  for code in ["91350100M000100Y44", "9135010", "9135０100M000100Y43"] {
+    let message = UsciCode::from_str(code).unwrap_err().to_string();
+    let err = parse(UsciCodeParser::new(), code).unwrap_err();
+    assert!(err.contains(&message), "{} / {}", err, message);
+    assert!(err.contains("'code'") || err.contains("<code>"), "{}", err);
+    assert!(cli_error(code).contains(&message), "{}", cli_error(code));
+  }
+}
+
+#[test]
+fn normalizes_only_when_not_strict() {
+  assert!(parse(UsciCodeParser::new(), "9135０100M000100Y43").is_err());
+  assert_eq!(parse(UsciCodeParser::new().strict(false), "9135０100M000100Y43").unwrap().as_str(), "91350100M000100Y43");
+}
+
+#[test]
+fn rejects_test_codes_on_request() {
+  let test_code = UsciCode::complete("91000000M000100Y4").unwrap();
+  assert!(test_code.is_test_code());
+  assert!(parse(UsciCodeParser::new(), test_code.as_str()).is_ok());
+  assert!(parse(UsciCodeParser::new().reject_test_codes(true), test_code.as_str()).unwrap_err().contains("Test Code Error"));
+}